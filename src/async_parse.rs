@@ -0,0 +1,58 @@
+//! Module with asynchronous spec parsing, using Tokio.
+
+use std::io;
+use std::panic;
+use std::path::Path;
+
+use crate::parse::Format;
+use crate::{ParseError, ParseOptions, Spec};
+
+/// Asynchronous version of [`read_from_file`].
+///
+/// The file is read using [`tokio::fs`], the parsing itself is done on
+/// Tokio's blocking thread pool.
+///
+/// [`read_from_file`]: crate::read_from_file
+pub async fn read_from_file_async<P: AsRef<Path>>(path: P) -> io::Result<Spec> {
+    let path = path.as_ref();
+    let format = Format::from_path(path);
+    #[cfg(not(all(feature = "json", feature = "yaml")))]
+    let format = format?;
+    let data = tokio::fs::read_to_string(path).await?;
+    #[cfg(all(feature = "json", feature = "yaml"))]
+    let format = format.unwrap_or_else(|_| Format::detect(&data));
+    spawn_blocking(move || ParseOptions::new().parse(format, &data))
+        .await
+        .map_err(Into::into)
+}
+
+/// Asynchronous version of [`Spec::from_json_str`], parsing on Tokio's
+/// blocking thread pool.
+#[cfg(feature = "json")]
+pub async fn from_json_str_async(input: String) -> Result<Spec, ParseError> {
+    spawn_blocking(move || Spec::from_json_str(&input)).await
+}
+
+/// Asynchronous version of [`Spec::from_yaml_str`], parsing on Tokio's
+/// blocking thread pool.
+#[cfg(feature = "yaml")]
+pub async fn from_yaml_str_async(input: String) -> Result<Spec, ParseError> {
+    spawn_blocking(move || Spec::from_yaml_str(&input)).await
+}
+
+/// Run `f` on Tokio's blocking thread pool, resuming the panic if `f` panics.
+async fn spawn_blocking<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(err) => match err.try_into_panic() {
+            Ok(payload) => panic::resume_unwind(payload),
+            // Blocking tasks can only be cancelled when the runtime is
+            // shutting down.
+            Err(err) => panic!("failed to parse specification: {}", err),
+        },
+    }
+}
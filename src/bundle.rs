@@ -0,0 +1,526 @@
+//! Module with bundling of external references.
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+
+use crate::parse::Format;
+#[cfg(feature = "yaml")]
+use crate::validate::parse_pointer;
+use crate::validate::unescape;
+use crate::{
+    BoolOr, Callback, Component, Components, Encoding, Example, Header, Link, MediaType, Operation,
+    Parameter, ParseError, ParseOptions, PathItem, Reference, RequestBody, Response, Schema,
+    SecurityScheme, Spec,
+};
+
+impl Spec {
+    /// Bundle all external references into a single self-contained
+    /// specification.
+    ///
+    /// All references to other files, e.g. `$ref: "./schemas/pet.yaml"` or
+    /// `$ref: "common.json#/components/schemas/Error"`, are loaded (relative
+    /// to `base_dir` for the references in this specification), added to the
+    /// [`Components`] and the reference is rewritten to point to the added
+    /// component. Targets referenced multiple times are only added once and
+    /// (recursive) references between files are supported. The name of the
+    /// component is based on the last token of the JSON pointer, or the name
+    /// of the file if the reference doesn't have a JSON pointer.
+    ///
+    /// References to URLs, e.g. `https://example.com/pet.json`, are not
+    /// supported.
+    ///
+    /// The referenced files are parsed with the limits of the default
+    /// [`ParseOptions`], see [`ParseOptions::max_depth`] and
+    /// [`ParseOptions::max_nodes`].
+    pub fn bundle(&self, base_dir: &Path) -> Result<Spec, BundleError> {
+        let mut spec = self.clone();
+        let mut bundler = Bundler {
+            base_dir,
+            names: component_names(&spec.components),
+            bundled: HashMap::new(),
+            documents: HashMap::new(),
+            components: Components::default(),
+        };
+        bundler.spec(&mut spec)?;
+        let components = bundler.components;
+        spec.components.schemas.extend(components.schemas);
+        spec.components.responses.extend(components.responses);
+        spec.components.parameters.extend(components.parameters);
+        spec.components.examples.extend(components.examples);
+        spec.components
+            .request_bodies
+            .extend(components.request_bodies);
+        spec.components.headers.extend(components.headers);
+        spec.components
+            .security_schemes
+            .extend(components.security_schemes);
+        spec.components.links.extend(components.links);
+        spec.components.callbacks.extend(components.callbacks);
+        spec.components.path_items.extend(components.path_items);
+        Ok(spec)
+    }
+}
+
+/// Returns the names of all `components`, along with the field they're
+/// defined in.
+fn component_names(components: &Components) -> HashSet<(&'static str, String)> {
+    let mut names = HashSet::new();
+    let mut add = |field, keys: &mut dyn Iterator<Item = &String>| {
+        names.extend(keys.map(|name| (field, name.clone())));
+    };
+    add(Schema::FIELD, &mut components.schemas.keys());
+    add(Response::FIELD, &mut components.responses.keys());
+    add(Parameter::FIELD, &mut components.parameters.keys());
+    add(Example::FIELD, &mut components.examples.keys());
+    add(RequestBody::FIELD, &mut components.request_bodies.keys());
+    add(Header::FIELD, &mut components.headers.keys());
+    add(
+        SecurityScheme::FIELD,
+        &mut components.security_schemes.keys(),
+    );
+    add(Link::FIELD, &mut components.links.keys());
+    add(Callback::FIELD, &mut components.callbacks.keys());
+    add(PathItem::FIELD, &mut components.path_items.keys());
+    names
+}
+
+/// Error returned by [`Spec::bundle`].
+#[derive(Debug)]
+pub enum BundleError {
+    /// Error reading a referenced file.
+    Io {
+        /// Path to the file.
+        path: PathBuf,
+        /// The I/O error.
+        err: io::Error,
+    },
+    /// Error parsing a referenced file, or its contents.
+    Parse {
+        /// Path to the file.
+        path: PathBuf,
+        /// The parsing error.
+        err: ParseError,
+    },
+    /// Reference that can't be bundled, either because the JSON pointer
+    /// doesn't point to an existing value or because it's a URL.
+    InvalidReference(String),
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BundleError::Io { path, err } => write!(f, "{}: {}", path.display(), err),
+            BundleError::Parse { path, err } => write!(f, "{}: {}", path.display(), err),
+            BundleError::InvalidReference(reference) => {
+                write!(f, "invalid reference `{}`", reference)
+            }
+        }
+    }
+}
+
+impl Error for BundleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BundleError::Io { err, .. } => Some(err),
+            BundleError::Parse { err, .. } => Some(err),
+            BundleError::InvalidReference(_) => None,
+        }
+    }
+}
+
+/// A loaded document.
+enum Document {
+    #[cfg(feature = "json")]
+    Json(serde_json::Value),
+    #[cfg(feature = "yaml")]
+    Yaml(serde_yaml::Value),
+}
+
+impl Document {
+    fn load(path: &Path) -> Result<Document, BundleError> {
+        let data = fs::read_to_string(path).map_err(|err| BundleError::Io {
+            path: path.to_owned(),
+            err,
+        })?;
+        let format = Format::from_path(path).map_err(|err| BundleError::Io {
+            path: path.to_owned(),
+            err,
+        })?;
+        // Apply the same limits as for the specification itself, protecting
+        // against e.g. excessive expansion of YAML aliases.
+        let options = ParseOptions::new();
+        let document = match format {
+            #[cfg(feature = "json")]
+            Format::Json => options.deserialize(format, &data).map(Document::Json),
+            #[cfg(feature = "yaml")]
+            Format::Yaml => options.deserialize(format, &data).map(Document::Yaml),
+        };
+        document.map_err(|err| BundleError::Parse {
+            path: path.to_owned(),
+            err,
+        })
+    }
+
+    /// Deserialize the value `pointer` points to, returns `None` if `pointer`
+    /// doesn't point to a value.
+    fn get<T: DeserializeOwned>(&self, pointer: &str) -> Option<Result<T, ParseError>> {
+        match self {
+            #[cfg(feature = "json")]
+            Document::Json(value) => {
+                let value = value.pointer(pointer)?;
+                Some(T::deserialize(value).map_err(ParseError::Json))
+            }
+            #[cfg(feature = "yaml")]
+            Document::Yaml(value) => {
+                let mut value = value;
+                for token in parse_pointer(pointer)? {
+                    value = match value {
+                        serde_yaml::Value::Mapping(map) => map.get(&token.into_owned().into())?,
+                        serde_yaml::Value::Sequence(seq) => {
+                            seq.get(token.parse::<usize>().ok()?)?
+                        }
+                        _ => return None,
+                    };
+                }
+                // serde_yaml can only deserialise from an owned `Value`.
+                Some(serde_yaml::from_value(value.clone()).map_err(ParseError::Yaml))
+            }
+        }
+    }
+}
+
+struct Bundler<'a> {
+    base_dir: &'a Path,
+    /// Names of the components, including the bundled ones, along with the
+    /// field they're defined in.
+    names: HashSet<(&'static str, String)>,
+    /// (field, file, JSON pointer) -> local reference.
+    bundled: HashMap<(&'static str, PathBuf, String), String>,
+    /// Loaded documents.
+    documents: HashMap<PathBuf, Document>,
+    /// Bundled components.
+    components: Components,
+}
+
+/// File the object being bundled is defined in, `None` for the specification
+/// itself.
+type Base<'a> = Option<&'a Path>;
+
+impl<'a> Bundler<'a> {
+    /// Bundle `reference` if it's an external reference, returning the local
+    /// reference to the bundled component. Returns `None` if `reference` is
+    /// a local reference in the specification itself.
+    fn bundle<T, F>(
+        &mut self,
+        base: Base,
+        reference: &str,
+        walk: F,
+    ) -> Result<Option<String>, BundleError>
+    where
+        T: Component + DeserializeOwned,
+        F: FnOnce(&mut Self, Base, &mut T) -> Result<(), BundleError>,
+    {
+        let (file, pointer) = reference.split_once('#').unwrap_or((reference, ""));
+        let path = if file.is_empty() {
+            match base {
+                Some(base) => base.to_owned(),
+                None => return Ok(None),
+            }
+        } else if file.contains("://") {
+            return Err(BundleError::InvalidReference(reference.to_owned()));
+        } else {
+            let dir = base.and_then(Path::parent).unwrap_or(self.base_dir);
+            let path = dir.join(file);
+            fs::canonicalize(&path).map_err(|err| BundleError::Io { path, err })?
+        };
+
+        let key = (T::FIELD, path, pointer.to_owned());
+        if let Some(local) = self.bundled.get(&key) {
+            return Ok(Some(local.clone()));
+        }
+        let name = self.component_name::<T>(&key.1, pointer);
+        let local = format!("#/components/{}/{}", T::FIELD, name);
+        let (_, path, _) = key.clone();
+        self.bundled.insert(key, local.clone());
+
+        if !self.documents.contains_key(&path) {
+            let document = Document::load(&path)?;
+            self.documents.insert(path.clone(), document);
+        }
+        let mut object: T = match self.documents[&path].get(pointer) {
+            Some(Ok(object)) => object,
+            Some(Err(err)) => return Err(BundleError::Parse { path, err }),
+            None => return Err(BundleError::InvalidReference(reference.to_owned())),
+        };
+        walk(self, Some(&path), &mut object)?;
+        T::insert(&mut self.components, name, object);
+        Ok(Some(local))
+    }
+
+    /// Returns a unique name for the component defined in `path` at
+    /// `pointer`.
+    fn component_name<T: Component>(&mut self, path: &Path, pointer: &str) -> String {
+        let name = match pointer.rsplit('/').next() {
+            Some(token) if !token.is_empty() => unescape(token).into_owned(),
+            _ => path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        };
+        // Component names must match `^[a-zA-Z0-9\.\-_]+$`.
+        let name: String = name
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect();
+        let mut unique = name.clone();
+        let mut n = 1;
+        while self.names.contains(&(T::FIELD, unique.clone())) || unique.is_empty() {
+            n += 1;
+            unique = format!("{}{}", name, n);
+        }
+        self.names.insert((T::FIELD, unique.clone()));
+        unique
+    }
+
+    /// Bundle `reference`, or if it's an inline object call `walk`.
+    fn reference<T, F>(
+        &mut self,
+        base: Base,
+        reference: &mut Reference<T>,
+        walk: F,
+    ) -> Result<(), BundleError>
+    where
+        T: Component + DeserializeOwned,
+        F: FnOnce(&mut Self, Base, &mut T) -> Result<(), BundleError>,
+    {
+        match (&reference.r#ref, &mut reference.object) {
+            (Some(r), _) => {
+                if let Some(local) = self.bundle(base, r, walk)? {
+                    reference.r#ref = Some(local);
+                }
+            }
+            (None, Some(object)) => walk(self, base, object)?,
+            (None, None) => {}
+        }
+        Ok(())
+    }
+
+    fn spec(&mut self, spec: &mut Spec) -> Result<(), BundleError> {
+        for path_item in spec.paths.values_mut() {
+            self.path_item(None, path_item)?;
+        }
+        for path_item in spec.webhooks.values_mut() {
+            self.path_item(None, path_item)?;
+        }
+
+        let components = &mut spec.components;
+        for schema in components.schemas.values_mut() {
+            self.schema(None, schema)?;
+        }
+        for response in components.responses.values_mut() {
+            self.reference(None, response, Self::response)?;
+        }
+        for parameter in components.parameters.values_mut() {
+            self.reference(None, parameter, Self::parameter)?;
+        }
+        for example in components.examples.values_mut() {
+            self.reference(None, example, |_, _, _| Ok(()))?;
+        }
+        for body in components.request_bodies.values_mut() {
+            self.reference(None, body, Self::request_body)?;
+        }
+        for header in components.headers.values_mut() {
+            self.reference(None, header, Self::header)?;
+        }
+        for scheme in components.security_schemes.values_mut() {
+            self.reference(None, scheme, |_, _, _| Ok(()))?;
+        }
+        for link in components.links.values_mut() {
+            self.reference(None, link, |_, _, _| Ok(()))?;
+        }
+        for callback in components.callbacks.values_mut() {
+            self.reference(None, callback, Self::callback)?;
+        }
+        for path_item in components.path_items.values_mut() {
+            self.path_item(None, path_item)?;
+        }
+        Ok(())
+    }
+
+    fn path_item(&mut self, base: Base, path_item: &mut PathItem) -> Result<(), BundleError> {
+        if let Some(reference) = &path_item.r#ref {
+            if let Some(local) = self.bundle(base, reference, Self::path_item)? {
+                path_item.r#ref = Some(local);
+            }
+        }
+        self.parameters(base, &mut path_item.parameters)?;
+        for (_, operation) in path_item.operations_mut() {
+            self.operation(base, operation)?;
+        }
+        Ok(())
+    }
+
+    fn operation(&mut self, base: Base, operation: &mut Operation) -> Result<(), BundleError> {
+        self.parameters(base, &mut operation.parameters)?;
+        if let Some(body) = &mut operation.request_body {
+            self.reference(base, body, Self::request_body)?;
+        }
+        if let Some(responses) = &mut operation.responses {
+            if let Some(response) = &mut responses.default {
+                self.reference(base, response, Self::response)?;
+            }
+            for response in responses.response.values_mut() {
+                self.reference(base, response, Self::response)?;
+            }
+        }
+        for callback in operation.callbacks.values_mut() {
+            self.reference(base, callback, Self::callback)?;
+        }
+        Ok(())
+    }
+
+    fn parameters(
+        &mut self,
+        base: Base,
+        parameters: &mut [Reference<Parameter>],
+    ) -> Result<(), BundleError> {
+        for parameter in parameters {
+            self.reference(base, parameter, Self::parameter)?;
+        }
+        Ok(())
+    }
+
+    fn parameter(&mut self, base: Base, parameter: &mut Parameter) -> Result<(), BundleError> {
+        if let Some(schema) = &mut parameter.schema {
+            self.schema(base, schema)?;
+        }
+        self.examples(base, &mut parameter.examples)?;
+        self.content(base, &mut parameter.content)
+    }
+
+    fn request_body(&mut self, base: Base, body: &mut RequestBody) -> Result<(), BundleError> {
+        self.content(base, &mut body.content)
+    }
+
+    fn response(&mut self, base: Base, response: &mut Response) -> Result<(), BundleError> {
+        self.headers(base, &mut response.headers)?;
+        self.content(base, &mut response.content)?;
+        for link in response.links.values_mut() {
+            self.reference(base, link, |_, _, _| Ok(()))?;
+        }
+        Ok(())
+    }
+
+    fn callback(&mut self, base: Base, callback: &mut Callback) -> Result<(), BundleError> {
+        for path_item in callback.expressions.values_mut() {
+            self.path_item(base, path_item)?;
+        }
+        Ok(())
+    }
+
+    fn headers(
+        &mut self,
+        base: Base,
+        headers: &mut HashMap<String, Reference<Header>>,
+    ) -> Result<(), BundleError> {
+        for header in headers.values_mut() {
+            self.reference(base, header, Self::header)?;
+        }
+        Ok(())
+    }
+
+    fn header(&mut self, base: Base, header: &mut Header) -> Result<(), BundleError> {
+        if let Some(schema) = &mut header.schema {
+            self.schema(base, schema)?;
+        }
+        self.examples(base, &mut header.examples)?;
+        self.content(base, &mut header.content)
+    }
+
+    fn examples(
+        &mut self,
+        base: Base,
+        examples: &mut HashMap<String, Reference<Example>>,
+    ) -> Result<(), BundleError> {
+        for example in examples.values_mut() {
+            self.reference(base, example, |_, _, _| Ok(()))?;
+        }
+        Ok(())
+    }
+
+    fn content(
+        &mut self,
+        base: Base,
+        content: &mut HashMap<String, MediaType>,
+    ) -> Result<(), BundleError> {
+        for media_type in content.values_mut() {
+            if let Some(schema) = &mut media_type.schema {
+                self.schema(base, schema)?;
+            }
+            self.examples(base, &mut media_type.examples)?;
+            for Encoding { headers, .. } in media_type.encoding.values_mut() {
+                self.headers(base, headers)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn schema(&mut self, base: Base, schema: &mut Schema) -> Result<(), BundleError> {
+        if let Some(reference) = &schema.r#ref {
+            if let Some(local) = self.bundle(base, reference, Self::schema)? {
+                schema.r#ref = Some(local);
+            }
+        }
+
+        let schemas = [
+            &mut schema.not,
+            &mut schema.r#if,
+            &mut schema.then,
+            &mut schema.r#else,
+            &mut schema.items,
+            &mut schema.contains,
+            &mut schema.property_names,
+            &mut schema.unevaluated_items,
+            &mut schema.unevaluated_properties,
+            &mut schema.content_schema,
+        ];
+        for subschema in schemas.into_iter().flatten() {
+            self.schema(base, subschema)?;
+        }
+        if let Some(BoolOr::Item(subschema)) = &mut schema.additional_properties {
+            self.schema(base, subschema)?;
+        }
+        let schemas = [&mut schema.all_of, &mut schema.any_of, &mut schema.one_of];
+        for subschemas in schemas.into_iter().flatten() {
+            for subschema in subschemas {
+                self.schema(base, subschema)?;
+            }
+        }
+        for subschema in &mut schema.prefix_items {
+            self.schema(base, subschema)?;
+        }
+        for subschema in schema.properties.iter_mut().flat_map(|p| p.values_mut()) {
+            self.schema(base, subschema)?;
+        }
+        let schemas = [
+            &mut schema.pattern_properties,
+            &mut schema.dependent_schemas,
+            &mut schema.defs,
+        ];
+        for subschemas in schemas {
+            for subschema in subschemas.values_mut() {
+                self.schema(base, subschema)?;
+            }
+        }
+        Ok(())
+    }
+}
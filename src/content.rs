@@ -0,0 +1,51 @@
+//! Module with content negotiation.
+
+use std::collections::HashMap;
+
+use crate::MediaType;
+
+/// Select the media type in `content` that applies to the media type
+/// `accept`, e.g. the `content` of a [`RequestBody`] or [`Response`].
+///
+/// As the keys in `content` can be media type ranges the most specific key
+/// is selected: an exact match (e.g. `text/plain`) is preferred over a subtype
+/// wildcard (e.g. `text/*`), which in turn is preferred over `*/*`. Media type
+/// parameters (e.g. `; charset=utf-8`) are ignored and the matching is case
+/// insensitive.
+///
+/// [`RequestBody`]: crate::RequestBody
+/// [`Response`]: crate::Response
+pub fn select_media_type<'a>(
+    content: &'a HashMap<String, MediaType>,
+    accept: &str,
+) -> Option<(&'a str, &'a MediaType)> {
+    let (r#type, subtype) = essence(accept).split_once('/')?;
+    content
+        .iter()
+        .filter_map(|(key, media_type)| {
+            let (key_type, key_subtype) = essence(key).split_once('/')?;
+            let specificity = if key_type == "*" && key_subtype == "*" {
+                0
+            } else if !key_type.eq_ignore_ascii_case(r#type) {
+                return None;
+            } else if key_subtype == "*" {
+                1
+            } else if key_subtype.eq_ignore_ascii_case(subtype) {
+                2
+            } else {
+                return None;
+            };
+            Some((specificity, &**key, media_type))
+        })
+        // Prefer the key that sorts first if multiple keys are equally
+        // specific (e.g. differ only in parameters), making this
+        // deterministic.
+        .max_by(|(s1, k1, _), (s2, k2, _)| s1.cmp(s2).then(k2.cmp(k1)))
+        .map(|(_, key, media_type)| (key, media_type))
+}
+
+/// Returns the media type without parameters, e.g. `text/plain` for
+/// `text/plain; charset=utf-8`.
+fn essence(media_type: &str) -> &str {
+    media_type.split(';').next().unwrap_or(media_type).trim()
+}
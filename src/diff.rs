@@ -0,0 +1,651 @@
+//! Module with comparing of specifications.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::validate::escape;
+use crate::{
+    HttpMethod, MediaType, Operation, Parameter, PathItem, Reference, RequestBody, Response,
+    Responses, Schema, Spec, Type,
+};
+
+/// Differences between two specifications, returned by [`Spec::diff`].
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct SpecDiff {
+    /// All changes, in the order they were found.
+    pub changes: Vec<Change>,
+}
+
+impl SpecDiff {
+    /// Returns `true` if there are no changes.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the backwards incompatible changes.
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| change.breaking)
+    }
+
+    /// Returns `true` if any of the changes is backwards incompatible.
+    pub fn is_breaking(&self) -> bool {
+        self.breaking().next().is_some()
+    }
+}
+
+/// A single change between two specifications, see [`SpecDiff`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Change {
+    /// Location of the change as JSON pointer, e.g. `/paths/~1pets/get`. For
+    /// removals this is the location in the old specification, otherwise the
+    /// location in the new specification.
+    pub location: String,
+    /// The kind of change.
+    pub kind: ChangeKind,
+    /// Whether or not the change is backwards incompatible for clients of the
+    /// API.
+    pub breaking: bool,
+    /// Description of the change, e.g. ``required parameter `limit` added``.
+    pub message: String,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`: {}", self.location, self.message)
+    }
+}
+
+/// A backwards incompatible change, returned by
+/// [`Spec::breaking_changes_since`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakingChange {
+    /// Location of the change as JSON pointer, see [`Change::location`].
+    pub location: String,
+    /// The kind of change.
+    pub kind: ChangeKind,
+    /// Description of the change, e.g. ``parameter `limit` became required``.
+    pub message: String,
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`: {}", self.location, self.message)
+    }
+}
+
+/// Kind of [`Change`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    /// Definition was added.
+    Added,
+    /// Definition was removed.
+    Removed,
+    /// Definition was modified.
+    Changed,
+}
+
+impl Spec {
+    /// Compare this specification with the `new` version of it.
+    ///
+    /// This compares the API surface that matters to clients:
+    ///  * the paths and operations in [`Spec::paths`],
+    ///  * the parameters (including the ones defined on the path item),
+    ///    request body and responses of the operations,
+    ///  * the schemas used by the above, following references. Schemas are
+    ///    compared on their `type`, `nullable`, `format`, `enum`, `required`,
+    ///    `properties` and `items`.
+    ///
+    /// Changes are classified as breaking if they can break existing clients,
+    /// e.g. removing an operation, adding a required parameter, narrowing the
+    /// type of a request schema or widening the type of a response schema.
+    ///
+    /// Documentation fields, such as descriptions, and [`Spec::webhooks`] are
+    /// not compared.
+    pub fn diff(&self, new: &Spec) -> SpecDiff {
+        let mut diff = Diff {
+            old: self,
+            new,
+            stack: Vec::new(),
+            changes: Vec::new(),
+        };
+        diff.paths();
+        SpecDiff {
+            changes: diff.changes,
+        }
+    }
+}
+
+impl Spec {
+    /// Returns the backwards incompatible changes made to this specification
+    /// since the `old` version of it, e.g. to fail a CI job.
+    ///
+    /// These are the breaking changes of [`Spec::diff`], including:
+    ///  * removed paths, operations, responses and media types,
+    ///  * added required parameters and parameters that became required,
+    ///  * removed properties and types in responses,
+    ///  * removed enum values and narrowed types in requests.
+    pub fn breaking_changes_since(&self, old: &Spec) -> Vec<BreakingChange> {
+        old.diff(self)
+            .changes
+            .into_iter()
+            .filter(|change| change.breaking)
+            .map(|change| BreakingChange {
+                location: change.location,
+                kind: change.kind,
+                message: change.message,
+            })
+            .collect()
+    }
+}
+
+/// Direction in which a schema is used, determines what changes are breaking.
+#[derive(Copy, Clone, PartialEq)]
+enum Direction {
+    /// Sent by the client, narrowing is breaking.
+    Request,
+    /// Sent by the server, widening is breaking.
+    Response,
+}
+
+struct Diff<'s> {
+    old: &'s Spec,
+    new: &'s Spec,
+    /// Pairs of schema references currently being compared, protects against
+    /// reference cycles.
+    stack: Vec<(&'s str, &'s str)>,
+    changes: Vec<Change>,
+}
+
+impl<'s> Diff<'s> {
+    fn change(&mut self, location: String, kind: ChangeKind, breaking: bool, message: String) {
+        let change = Change {
+            location,
+            kind,
+            breaking,
+            message,
+        };
+        // Parameters defined on the path item are compared for every operation.
+        if !self.changes.contains(&change) {
+            self.changes.push(change);
+        }
+    }
+
+    fn paths(&mut self) {
+        for (path, old) in &self.old.paths {
+            let location = join("/paths", path);
+            match self.new.paths.get(path) {
+                Some(new) => {
+                    let old = old.resolve(self.old);
+                    let new = new.resolve(self.new);
+                    self.path_item(&location, old, new);
+                }
+                None => self.change(location, ChangeKind::Removed, true, "path removed".into()),
+            }
+        }
+        for path in self.new.paths.keys() {
+            if !self.old.paths.contains_key(path) {
+                let location = join("/paths", path);
+                self.change(location, ChangeKind::Added, false, "path added".into());
+            }
+        }
+    }
+
+    fn path_item(&mut self, location: &str, old: &'s PathItem, new: &'s PathItem) {
+        for method in HttpMethod::ALL {
+            let location = join(location, method.as_str());
+            match (old.operation(method), new.operation(method)) {
+                (Some(old_op), Some(new_op)) => {
+                    self.operation(&location, (old, old_op), (new, new_op));
+                }
+                (Some(_), None) => {
+                    let message = "operation removed".into();
+                    self.change(location, ChangeKind::Removed, true, message);
+                }
+                (None, Some(_)) => {
+                    let message = "operation added".into();
+                    self.change(location, ChangeKind::Added, false, message);
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    fn operation(
+        &mut self,
+        location: &str,
+        old: (&'s PathItem, &'s Operation),
+        new: (&'s PathItem, &'s Operation),
+    ) {
+        self.parameters(location, old, new);
+        self.request_body(
+            &join(location, "requestBody"),
+            old.1.request_body.as_ref(),
+            new.1.request_body.as_ref(),
+        );
+        self.responses(
+            &join(location, "responses"),
+            old.1.responses.as_ref(),
+            new.1.responses.as_ref(),
+        );
+    }
+
+    fn parameters(
+        &mut self,
+        location: &str,
+        old: (&'s PathItem, &'s Operation),
+        new: (&'s PathItem, &'s Operation),
+    ) {
+        let path_location = location.rsplit_once('/').map_or("", |(path, _)| path);
+        let old_parameters = parameters(self.old, path_location, location, old);
+        let new_parameters = parameters(self.new, path_location, location, new);
+
+        for (old_location, old) in &old_parameters {
+            let new = new_parameters
+                .iter()
+                .find(|(_, new)| new.name == old.name && new.r#in == old.r#in);
+            let (new_location, new) = match new {
+                Some(new) => new,
+                None => {
+                    let message = format!("parameter `{}` removed", old.name);
+                    self.change(old_location.clone(), ChangeKind::Removed, false, message);
+                    continue;
+                }
+            };
+            if !old.required && new.required {
+                let message = format!("parameter `{}` became required", new.name);
+                let location = join(new_location, "required");
+                self.change(location, ChangeKind::Changed, true, message);
+            } else if old.required && !new.required {
+                let message = format!("parameter `{}` became optional", new.name);
+                let location = join(new_location, "required");
+                self.change(location, ChangeKind::Changed, false, message);
+            }
+            if let (Some(old), Some(new)) = (&old.schema, &new.schema) {
+                let location = join(new_location, "schema");
+                self.schema(&location, old, new, Direction::Request);
+            }
+            self.content(new_location, &old.content, &new.content, Direction::Request);
+        }
+
+        for (new_location, new) in &new_parameters {
+            let exists = old_parameters
+                .iter()
+                .any(|(_, old)| old.name == new.name && old.r#in == new.r#in);
+            if !exists {
+                let (breaking, message) = if new.required {
+                    (true, format!("required parameter `{}` added", new.name))
+                } else {
+                    (false, format!("parameter `{}` added", new.name))
+                };
+                self.change(new_location.clone(), ChangeKind::Added, breaking, message);
+            }
+        }
+    }
+
+    fn request_body(
+        &mut self,
+        location: &str,
+        old: Option<&'s Reference<RequestBody>>,
+        new: Option<&'s Reference<RequestBody>>,
+    ) {
+        let old = old.and_then(|body| body.resolve(self.old));
+        let new = new.and_then(|body| body.resolve(self.new));
+        match (old, new) {
+            (Some(old), Some(new)) => {
+                if !old.required && new.required {
+                    let message = "request body became required".into();
+                    self.change(
+                        join(location, "required"),
+                        ChangeKind::Changed,
+                        true,
+                        message,
+                    );
+                }
+                self.content(location, &old.content, &new.content, Direction::Request);
+            }
+            (Some(_), None) => {
+                let message = "request body removed".into();
+                self.change(location.to_owned(), ChangeKind::Removed, false, message);
+            }
+            (None, Some(new)) => {
+                let (breaking, message) = if new.required {
+                    (true, "required request body added".into())
+                } else {
+                    (false, "request body added".into())
+                };
+                self.change(location.to_owned(), ChangeKind::Added, breaking, message);
+            }
+            (None, None) => {}
+        }
+    }
+
+    fn responses(
+        &mut self,
+        location: &str,
+        old: Option<&'s Responses>,
+        new: Option<&'s Responses>,
+    ) {
+        let old: Vec<_> = old.into_iter().flat_map(Responses::iter_all).collect();
+        let new: Vec<_> = new.into_iter().flat_map(Responses::iter_all).collect();
+        for (status, old_response) in &old {
+            let location = join(location, status);
+            match new.iter().find(|(s, _)| s == status) {
+                Some((_, new_response)) => self.response(&location, old_response, new_response),
+                None => {
+                    let message = format!("response `{}` removed", status);
+                    self.change(location, ChangeKind::Removed, true, message);
+                }
+            }
+        }
+        for (status, _) in &new {
+            if !old.iter().any(|(s, _)| s == status) {
+                let message = format!("response `{}` added", status);
+                self.change(join(location, status), ChangeKind::Added, false, message);
+            }
+        }
+    }
+
+    fn response(
+        &mut self,
+        location: &str,
+        old: &'s Reference<Response>,
+        new: &'s Reference<Response>,
+    ) {
+        if let (Some(old), Some(new)) = (old.resolve(self.old), new.resolve(self.new)) {
+            self.content(location, &old.content, &new.content, Direction::Response);
+        }
+    }
+
+    /// Compare the `content` field of an object at `location`.
+    fn content(
+        &mut self,
+        location: &str,
+        old: &'s HashMap<String, MediaType>,
+        new: &'s HashMap<String, MediaType>,
+        direction: Direction,
+    ) {
+        let location = join(location, "content");
+        for (media_type, old) in sorted(old) {
+            let location = join(&location, media_type);
+            match new.get(media_type) {
+                Some(new) => {
+                    if let (Some(old), Some(new)) = (&old.schema, &new.schema) {
+                        self.schema(&join(&location, "schema"), old, new, direction);
+                    }
+                }
+                None => {
+                    let message = format!("media type `{}` removed", media_type);
+                    self.change(location, ChangeKind::Removed, true, message);
+                }
+            }
+        }
+        for (media_type, _) in sorted(new) {
+            if !old.contains_key(media_type) {
+                let message = format!("media type `{}` added", media_type);
+                let location = join(&location, media_type);
+                self.change(location, ChangeKind::Added, false, message);
+            }
+        }
+    }
+
+    fn schema(&mut self, location: &str, old: &'s Schema, new: &'s Schema, direction: Direction) {
+        match (&old.r#ref, &new.r#ref) {
+            (None, None) => self.resolved_schema(location, old, new, direction),
+            (old_ref, new_ref) => {
+                let old_ref = old_ref.as_deref().unwrap_or("");
+                let new_ref = new_ref.as_deref().unwrap_or("");
+                if self.stack.contains(&(old_ref, new_ref)) {
+                    return;
+                }
+                let old_schema = resolve(self.old, old);
+                let new_schema = resolve(self.new, new);
+                match (old_schema, new_schema) {
+                    (Some(old), Some(new)) => {
+                        self.stack.push((old_ref, new_ref));
+                        self.resolved_schema(location, old, new, direction);
+                        self.stack.pop();
+                    }
+                    _ if old_ref != new_ref => {
+                        let message =
+                            format!("reference changed from `{}` to `{}`", old_ref, new_ref);
+                        self.change(location.to_owned(), ChangeKind::Changed, false, message);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn resolved_schema(
+        &mut self,
+        location: &str,
+        old: &'s Schema,
+        new: &'s Schema,
+        direction: Direction,
+    ) {
+        let old_types = types(old);
+        let new_types = types(new);
+        if old_types != new_types {
+            let breaking = match direction {
+                Direction::Request => !is_subset(&old_types, &new_types),
+                Direction::Response => !is_subset(&new_types, &old_types),
+            };
+            let message = format!(
+                "type changed from {} to {}",
+                fmt_types(&old_types),
+                fmt_types(&new_types)
+            );
+            self.change(
+                join(location, "type"),
+                ChangeKind::Changed,
+                breaking,
+                message,
+            );
+        }
+
+        if old.format != new.format {
+            let breaking = match (&old.format, &new.format) {
+                (Some(_), Some(_)) => true,
+                (None, Some(_)) => direction == Direction::Request,
+                (Some(_), None) => direction == Direction::Response,
+                (None, None) => false,
+            };
+            let message = format!(
+                "format changed from {} to {}",
+                fmt_option(old.format.as_ref()),
+                fmt_option(new.format.as_ref())
+            );
+            self.change(
+                join(location, "format"),
+                ChangeKind::Changed,
+                breaking,
+                message,
+            );
+        }
+
+        let enum_location = join(location, "enum");
+        if !old.r#enum.is_empty() || !new.r#enum.is_empty() {
+            for value in &old.r#enum {
+                if !new.r#enum.is_empty() && !new.r#enum.contains(value) {
+                    let breaking = direction == Direction::Request;
+                    let message = format!("enum value `{}` removed", value);
+                    self.change(
+                        enum_location.clone(),
+                        ChangeKind::Removed,
+                        breaking,
+                        message,
+                    );
+                }
+            }
+            for value in &new.r#enum {
+                if !old.r#enum.is_empty() && !old.r#enum.contains(value) {
+                    let breaking = direction == Direction::Response;
+                    let message = format!("enum value `{}` added", value);
+                    self.change(enum_location.clone(), ChangeKind::Added, breaking, message);
+                }
+            }
+            if old.r#enum.is_empty() {
+                let breaking = direction == Direction::Request;
+                let message = "enum added".into();
+                self.change(enum_location, ChangeKind::Added, breaking, message);
+            } else if new.r#enum.is_empty() {
+                let breaking = direction == Direction::Response;
+                let message = "enum removed".into();
+                self.change(enum_location, ChangeKind::Removed, breaking, message);
+            }
+        }
+
+        for property in &new.required {
+            if !old.is_required(property) {
+                let breaking = direction == Direction::Request;
+                let message = format!("property `{}` became required", property);
+                self.change(
+                    join(location, "required"),
+                    ChangeKind::Changed,
+                    breaking,
+                    message,
+                );
+            }
+        }
+        for property in &old.required {
+            if !new.is_required(property) {
+                let breaking = direction == Direction::Response;
+                let message = format!("property `{}` became optional", property);
+                self.change(
+                    join(location, "required"),
+                    ChangeKind::Changed,
+                    breaking,
+                    message,
+                );
+            }
+        }
+
+        let properties_location = join(location, "properties");
+        let old_properties = old.properties.iter().flatten();
+        let new_properties = new.properties.as_ref();
+        for (name, old) in old_properties {
+            let location = join(&properties_location, name);
+            match new_properties.and_then(|properties| properties.get(name)) {
+                Some(new) => self.schema(&location, old, new, direction),
+                None => {
+                    let breaking = direction == Direction::Response;
+                    let message = format!("property `{}` removed", name);
+                    self.change(location, ChangeKind::Removed, breaking, message);
+                }
+            }
+        }
+        for name in new_properties
+            .into_iter()
+            .flat_map(|properties| properties.keys())
+        {
+            if !old
+                .properties
+                .as_ref()
+                .is_some_and(|p| p.contains_key(name))
+            {
+                let message = format!("property `{}` added", name);
+                let location = join(&properties_location, name);
+                self.change(location, ChangeKind::Added, false, message);
+            }
+        }
+
+        if let (Some(old), Some(new)) = (&old.items, &new.items) {
+            self.schema(&join(location, "items"), old, new, direction);
+        }
+    }
+}
+
+/// Returns all parameters of `operation`, including the ones defined on the
+/// path item that are not overridden by the operation, along with their
+/// location.
+fn parameters<'s>(
+    spec: &'s Spec,
+    path_location: &str,
+    operation_location: &str,
+    (path_item, operation): (&'s PathItem, &'s Operation),
+) -> Vec<(String, &'s Parameter)> {
+    let resolve = |location: &str, parameters: &'s [Reference<Parameter>]| {
+        parameters
+            .iter()
+            .enumerate()
+            .filter_map(|(i, parameter)| {
+                let location = format!("{}/parameters/{}", location, i);
+                Some((location, parameter.resolve(spec)?))
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut parameters = resolve(operation_location, &operation.parameters);
+    for (location, parameter) in resolve(path_location, &path_item.parameters) {
+        let overridden = parameters
+            .iter()
+            .any(|(_, p)| p.name == parameter.name && p.r#in == parameter.r#in);
+        if !overridden {
+            parameters.push((location, parameter));
+        }
+    }
+    parameters
+}
+
+/// Resolves the `$ref` of `schema`, if any.
+fn resolve<'s>(spec: &'s Spec, schema: &'s Schema) -> Option<&'s Schema> {
+    match &schema.r#ref {
+        Some(reference) => spec.resolve_ref(reference),
+        None => Some(schema),
+    }
+}
+
+/// Returns the types allowed by `schema`, including [`Type::Null`] if it's
+/// nullable. An empty list allows all types.
+fn types(schema: &Schema) -> Vec<Type> {
+    let mut types = schema.r#type.clone();
+    if schema.nullable && !types.is_empty() && !types.contains(&Type::Null) {
+        types.push(Type::Null);
+    }
+    types.sort_by_key(Type::as_str);
+    types
+}
+
+/// Returns `true` if all values allowed by `types` are allowed by `other`.
+fn is_subset(types: &[Type], other: &[Type]) -> bool {
+    if other.is_empty() {
+        return true;
+    } else if types.is_empty() {
+        return false;
+    }
+    types
+        .iter()
+        .all(|t| other.contains(t) || (*t == Type::Integer && other.contains(&Type::Number)))
+}
+
+fn fmt_types(types: &[Type]) -> String {
+    if types.is_empty() {
+        return "any".to_owned();
+    }
+    let types: Vec<String> = types.iter().map(|t| format!("`{}`", t)).collect();
+    types.join("/")
+}
+
+fn fmt_option<T: fmt::Display>(value: Option<&T>) -> String {
+    match value {
+        Some(value) => format!("`{}`", value),
+        None => "none".to_owned(),
+    }
+}
+
+/// Returns the entries of `map` sorted by key, for a deterministic order of
+/// the changes.
+fn sorted<V>(map: &HashMap<String, V>) -> Vec<(&str, &V)> {
+    let mut entries: Vec<_> = map.iter().map(|(k, v)| (&**k, v)).collect();
+    entries.sort_unstable_by_key(|(k, _)| *k);
+    entries
+}
+
+/// Join JSON pointer `location` with `token`.
+fn join(location: &str, token: &str) -> String {
+    format!("{}/{}", location, escape(token))
+}
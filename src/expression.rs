@@ -0,0 +1,170 @@
+//! Module with parsing and formatting of [`RuntimeExpression`]s.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use crate::{Any, ExpressionOrValue, ExpressionSource, RuntimeExpression, Value};
+
+impl FromStr for RuntimeExpression {
+    type Err = ExpressionError;
+
+    fn from_str(expression: &str) -> Result<RuntimeExpression, ExpressionError> {
+        match expression {
+            "$url" => Ok(RuntimeExpression::Url),
+            "$method" => Ok(RuntimeExpression::Method),
+            "$statusCode" => Ok(RuntimeExpression::StatusCode),
+            _ => {
+                if let Some(source) = expression.strip_prefix("$request.") {
+                    source.parse().map(RuntimeExpression::Request)
+                } else if let Some(source) = expression.strip_prefix("$response.") {
+                    source.parse().map(RuntimeExpression::Response)
+                } else {
+                    Err(ExpressionError::new("unknown expression"))
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for ExpressionSource {
+    type Err = ExpressionError;
+
+    fn from_str(source: &str) -> Result<ExpressionSource, ExpressionError> {
+        if let Some(token) = source.strip_prefix("header.") {
+            if token.is_empty() || !token.bytes().all(is_tchar) {
+                return Err(ExpressionError::new("invalid header name"));
+            }
+            Ok(ExpressionSource::Header(token.to_owned()))
+        } else if let Some(name) = source.strip_prefix("query.") {
+            Ok(ExpressionSource::Query(name.to_owned()))
+        } else if let Some(name) = source.strip_prefix("path.") {
+            Ok(ExpressionSource::Path(name.to_owned()))
+        } else if source == "body" {
+            Ok(ExpressionSource::Body(None))
+        } else if let Some(pointer) = source.strip_prefix("body#") {
+            if !is_json_pointer(pointer) {
+                return Err(ExpressionError::new("invalid JSON pointer"));
+            }
+            Ok(ExpressionSource::Body(Some(pointer.to_owned())))
+        } else {
+            Err(ExpressionError::new("unknown source"))
+        }
+    }
+}
+
+/// See the `tchar` rule in [RFC7230].
+///
+/// [RFC7230]: https://tools.ietf.org/html/rfc7230#section-3.2.6
+fn is_tchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+/// See [RFC6901].
+///
+/// [RFC6901]: https://tools.ietf.org/html/rfc6901
+fn is_json_pointer(pointer: &str) -> bool {
+    if !(pointer.is_empty() || pointer.starts_with('/')) {
+        return false;
+    }
+    let mut chars = pointer.chars();
+    while let Some(c) = chars.next() {
+        if c == '~' && !matches!(chars.next(), Some('0' | '1')) {
+            return false;
+        }
+    }
+    true
+}
+
+impl fmt::Display for RuntimeExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuntimeExpression::Url => f.write_str("$url"),
+            RuntimeExpression::Method => f.write_str("$method"),
+            RuntimeExpression::StatusCode => f.write_str("$statusCode"),
+            RuntimeExpression::Request(source) => write!(f, "$request.{}", source),
+            RuntimeExpression::Response(source) => write!(f, "$response.{}", source),
+        }
+    }
+}
+
+impl fmt::Display for ExpressionSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExpressionSource::Header(token) => write!(f, "header.{}", token),
+            ExpressionSource::Query(name) => write!(f, "query.{}", name),
+            ExpressionSource::Path(name) => write!(f, "path.{}", name),
+            ExpressionSource::Body(None) => f.write_str("body"),
+            ExpressionSource::Body(Some(pointer)) => write!(f, "body#{}", pointer),
+        }
+    }
+}
+
+impl Serialize for RuntimeExpression {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for RuntimeExpression {
+    fn deserialize<D>(deserializer: D) -> Result<RuntimeExpression, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let expression = String::deserialize(deserializer)?;
+        expression.parse().map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for ExpressionOrValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ExpressionOrValue::Expression(expression) => expression.serialize(serializer),
+            ExpressionOrValue::Value(value) => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ExpressionOrValue {
+    fn deserialize<D>(deserializer: D) -> Result<ExpressionOrValue, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Any::deserialize(deserializer)?;
+        if let Value::String(expression) = &value {
+            if let Ok(expression) = expression.parse() {
+                return Ok(ExpressionOrValue::Expression(expression));
+            }
+        }
+        Ok(ExpressionOrValue::Value(value))
+    }
+}
+
+/// Error returned when parsing a [`RuntimeExpression`] fails.
+#[derive(Debug)]
+pub struct ExpressionError {
+    message: &'static str,
+}
+
+impl ExpressionError {
+    const fn new(message: &'static str) -> ExpressionError {
+        ExpressionError { message }
+    }
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid runtime expression: {}", self.message)
+    }
+}
+
+impl Error for ExpressionError {}
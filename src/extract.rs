@@ -0,0 +1,148 @@
+//! Module with extracting inline schemas into components.
+
+use crate::validate::{escape, unescape};
+use crate::{Schema, Spec, Visitor, VisitorMut};
+
+impl Spec {
+    /// Extract inline schemas that are used multiple times into
+    /// [`Components::schemas`], replacing them with a reference to the
+    /// component.
+    ///
+    /// Inline schemas identical to an existing component schema are replaced
+    /// with a reference to that component. Only object schemas, i.e. schemas
+    /// with `properties`, `allOf`, `anyOf` or `oneOf`, are extracted, trivial
+    /// schemas such as `type: string` are left as is.
+    ///
+    /// The name of the component is based on the `title` of the schema, or
+    /// the name of the property it's used in, see
+    /// [`Spec::extract_inline_schemas_with`] to use another naming strategy.
+    ///
+    /// [`Components::schemas`]: crate::Components::schemas
+    pub fn extract_inline_schemas(&mut self) {
+        self.extract_inline_schemas_with(default_name);
+    }
+
+    /// Same as [`Spec::extract_inline_schemas`], but uses `name` to name the
+    /// extracted schemas.
+    ///
+    /// `name` is called with the location of the first usage of the schema as
+    /// JSON pointer (e.g. `/paths/~1pets/get/responses/200`) and the schema
+    /// itself. If the returned name is already used a number is appended to
+    /// make it unique.
+    pub fn extract_inline_schemas_with<F>(&mut self, mut name: F)
+    where
+        F: FnMut(&str, &Schema) -> String,
+    {
+        let existing: Vec<(String, Schema)> = self
+            .components
+            .schemas
+            .iter()
+            .filter(|(_, schema)| is_extractable(schema))
+            .map(|(name, schema)| (name.clone(), schema.clone()))
+            .collect();
+        for (name, schema) in existing {
+            replace(self, &schema, &name);
+        }
+
+        loop {
+            let mut counter = Counter(Vec::new());
+            self.walk(&mut counter);
+            let (schema, location) = match counter.0.into_iter().find(|(_, _, count)| *count > 1) {
+                Some((schema, location, _)) => (schema, location),
+                None => break,
+            };
+
+            let name = name(&location, &schema);
+            let mut unique = name.clone();
+            let mut n = 1;
+            while self.components.schemas.contains_key(&unique) || unique.is_empty() {
+                n += 1;
+                unique = format!("{}{}", name, n);
+            }
+            replace(self, &schema, &unique);
+            self.components.schemas.insert(unique, schema);
+        }
+    }
+}
+
+/// Returns `true` if `schema` is worth extracting into a component.
+fn is_extractable(schema: &Schema) -> bool {
+    schema.r#ref.is_none()
+        && (schema.properties.as_ref().is_some_and(|p| !p.is_empty())
+            || [&schema.all_of, &schema.any_of, &schema.one_of]
+                .into_iter()
+                .any(|schemas| schemas.as_ref().is_some_and(|s| !s.is_empty())))
+}
+
+/// Returns `true` if `location` points to a schema in
+/// [`Components::schemas`] itself, rather than a subschema.
+///
+/// [`Components::schemas`]: crate::Components::schemas
+fn is_component(location: &str) -> bool {
+    location
+        .strip_prefix("/components/schemas/")
+        .is_some_and(|name| !name.contains('/'))
+}
+
+/// Counts the inline schemas, along with the location of the first usage.
+struct Counter(Vec<(Schema, String, usize)>);
+
+impl Visitor<'_> for Counter {
+    fn visit_schema(&mut self, location: &str, schema: &Schema) {
+        if is_component(location) || !is_extractable(schema) {
+            return;
+        }
+        match self.0.iter_mut().find(|(s, _, _)| s == schema) {
+            Some((_, _, count)) => *count += 1,
+            None => self.0.push((schema.clone(), location.to_owned(), 1)),
+        }
+    }
+}
+
+/// Replace all inline schemas equal to `target` with a reference to the
+/// component schema `name`.
+fn replace(spec: &mut Spec, target: &Schema, name: &str) {
+    struct Replace<'a> {
+        target: &'a Schema,
+        reference: String,
+    }
+
+    impl VisitorMut for Replace<'_> {
+        fn visit_schema(&mut self, location: &str, schema: &mut Schema) {
+            if !is_component(location) && schema == self.target {
+                *schema = Schema {
+                    r#ref: Some(self.reference.clone()),
+                    ..Schema::default()
+                };
+            }
+        }
+    }
+
+    spec.walk_mut(&mut Replace {
+        target,
+        reference: format!("#/components/schemas/{}", escape(name)),
+    });
+}
+
+/// Name based on the `title` of the schema, or the property it's defined in.
+fn default_name(location: &str, schema: &Schema) -> String {
+    let name = match (&schema.title, location.rsplit_once("/properties/")) {
+        (Some(title), _) => unescape(title).into_owned(),
+        (None, Some((_, property))) if !property.contains('/') => {
+            let property = unescape(property);
+            let mut chars = property.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        }
+        (None, _) => "Schema".to_owned(),
+    };
+    // Component names must match `^[a-zA-Z0-9\.\-_]+$`.
+    name.chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+            _ => '_',
+        })
+        .collect()
+}
@@ -0,0 +1,87 @@
+//! Module with fetching specs over HTTP(S).
+
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+use reqwest::header::CONTENT_TYPE;
+
+use crate::parse::Format;
+use crate::{ParseError, ParseOptions, Spec};
+
+/// Fetch and parse a JSON or YAML [Open API Specification] from `url`.
+///
+/// The format is determined by the `Content-Type` header of the response,
+/// falling back to the extension of the URL's path (e.g. `.json`) and finally
+/// to the contents of the document (see [`read_from_reader`]). Redirects are
+/// followed.
+///
+/// This uses a blocking HTTP client, it must not be called from within an
+/// asynchronous runtime.
+///
+/// [Open API Specification]: Spec
+/// [`read_from_reader`]: crate::read_from_reader
+pub fn read_from_url(url: &str) -> Result<Spec, FetchError> {
+    let response = reqwest::blocking::get(url).map_err(FetchError::Http)?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(FetchError::Status(status.as_u16()));
+    }
+
+    let format = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(format_from_content_type)
+        .or_else(|| Format::from_path(Path::new(response.url().path())).ok());
+    let input = response.text().map_err(FetchError::Http)?;
+    let format = format.unwrap_or_else(|| Format::detect(&input));
+    ParseOptions::new()
+        .parse(format, &input)
+        .map_err(FetchError::Parse)
+}
+
+/// Returns the format based on the `Content-Type`, e.g. `application/json`.
+fn format_from_content_type(content_type: &str) -> Option<Format> {
+    let media_type = content_type.split(';').next()?.trim();
+    match media_type.to_ascii_lowercase() {
+        #[cfg(feature = "json")]
+        m if m == "application/json" || m.ends_with("+json") => Some(Format::Json),
+        #[cfg(feature = "yaml")]
+        m if m.ends_with("/yaml") || m.ends_with("/x-yaml") || m.ends_with("+yaml") => {
+            Some(Format::Yaml)
+        }
+        _ => None,
+    }
+}
+
+/// Error returned by [`read_from_url`].
+#[derive(Debug)]
+pub enum FetchError {
+    /// Error making the request or reading the response.
+    Http(reqwest::Error),
+    /// The server responded with a non-successful status code, e.g. `404`.
+    Status(u16),
+    /// Error parsing the document.
+    Parse(ParseError),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::Http(err) => write!(f, "failed to fetch specification: {}", err),
+            FetchError::Status(status) => write!(f, "unexpected HTTP status code: {}", status),
+            FetchError::Parse(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for FetchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchError::Http(err) => Some(err),
+            FetchError::Status(_) => None,
+            FetchError::Parse(err) => Some(err),
+        }
+    }
+}
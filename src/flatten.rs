@@ -0,0 +1,251 @@
+//! Module with flattening of `allOf` schemas.
+
+use indexmap::IndexMap;
+
+use crate::validate::escape;
+use crate::{MergeConflict, Schema, Spec};
+
+/// Maximum depth of (nested) `allOf` schemas and references, protects against
+/// recursive schemas.
+const MAX_DEPTH: usize = 32;
+
+impl Schema {
+    /// Flatten the `allOf` composition of this schema into a single schema.
+    ///
+    /// Each schema in `allOf` is resolved (following `$ref`s using `spec`),
+    /// flattened itself and merged into this schema:
+    ///  * `properties`, `patternProperties`, `dependentSchemas` and
+    ///    `dependentRequired` are combined, the same property must be
+    ///    identical in all schemas.
+    ///  * `required` is combined.
+    ///  * `type` and `enum` are intersected.
+    ///  * For numeric constraints, such as `maximum` and `minLength`, the
+    ///    strictest value is used.
+    ///  * Annotations, such as `title` and `description`, of this schema take
+    ///    precedence over the ones in `allOf`.
+    ///  * All other keywords, including `oneOf` and `anyOf`, must be
+    ///    identical if defined in multiple schemas.
+    ///
+    /// If any of the schemas conflict an error is returned with the location
+    /// of the conflicting keyword relative to this schema, e.g.
+    /// `/allOf/1/properties/id`. References that can't be resolved are kept
+    /// in `allOf`.
+    pub fn flatten_all_of(&self, spec: &Spec) -> Result<Schema, MergeConflict> {
+        flatten(self, spec, "", MAX_DEPTH)
+    }
+}
+
+fn flatten(
+    schema: &Schema,
+    spec: &Spec,
+    location: &str,
+    depth: usize,
+) -> Result<Schema, MergeConflict> {
+    let mut result = schema.clone();
+    let all_of = match result.all_of.take() {
+        Some(all_of) if depth != 0 => all_of,
+        all_of => {
+            result.all_of = all_of;
+            return Ok(result);
+        }
+    };
+
+    let mut unresolved = Vec::new();
+    for (i, member) in all_of.into_iter().enumerate() {
+        let location = format!("{}/allOf/{}", location, i);
+        let mut member = member;
+        if let Some(reference) = &member.r#ref {
+            match resolve(spec, reference, depth) {
+                Some(target) => {
+                    let target = flatten(target, spec, &location, depth - 1)?;
+                    merge(&mut result, target, &location)?;
+                    member.r#ref = None;
+                }
+                None => {
+                    unresolved.push(member);
+                    continue;
+                }
+            }
+        }
+        let member = flatten(&member, spec, &location, depth - 1)?;
+        merge(&mut result, member, &location)?;
+    }
+    if !unresolved.is_empty() {
+        result.all_of = Some(unresolved);
+    }
+    Ok(result)
+}
+
+/// Resolve `reference`, following references to references.
+fn resolve<'a>(spec: &'a Spec, reference: &str, depth: usize) -> Option<&'a Schema> {
+    let mut schema = spec.resolve_ref::<Schema>(reference)?;
+    for _ in 0..depth {
+        match &schema.r#ref {
+            Some(reference) => schema = spec.resolve_ref(reference)?,
+            None => return Some(schema),
+        }
+    }
+    None
+}
+
+/// Merge `member` into `result`.
+fn merge(result: &mut Schema, member: Schema, location: &str) -> Result<(), MergeConflict> {
+    let conflict = |keyword: &str| MergeConflict {
+        location: format!("{}/{}", location, keyword),
+    };
+
+    /// Use the first defined value, for annotations.
+    macro_rules! first {
+        ($( $field: ident ),*) => {
+            $(
+            if result.$field.is_none() {
+                result.$field = member.$field;
+            }
+            )*
+        };
+    }
+    first!(
+        comment,
+        title,
+        description,
+        default,
+        example,
+        external_docs,
+        xml
+    );
+    if result.examples.is_empty() {
+        result.examples = member.examples;
+    }
+    result.deprecated |= member.deprecated;
+    result.read_only |= member.read_only;
+    result.write_only |= member.write_only;
+    for (name, value) in member.extensions {
+        result.extensions.entry(name).or_insert(value);
+    }
+
+    /// Use the strictest value, i.e. the lowest maximum or highest minimum.
+    macro_rules! strictest {
+        ($( $field: ident => $pick: ident ),*) => {
+            $(
+            result.$field = match (result.$field, member.$field) {
+                (Some(a), Some(b)) => Some(a.$pick(b)),
+                (a, b) => a.or(b),
+            };
+            )*
+        };
+    }
+    strictest!(
+        maximum => min,
+        exclusive_maximum => min,
+        minimum => max,
+        exclusive_minimum => max,
+        max_length => min,
+        min_length => max,
+        max_items => min,
+        min_items => max,
+        max_contains => min,
+        min_contains => max,
+        max_properties => min,
+        min_properties => max
+    );
+    result.unique_items |= member.unique_items;
+
+    /// Values must be identical if defined in both schemas.
+    macro_rules! same {
+        ($( $field: ident, $keyword: expr );* $(;)?) => {
+            $(
+            match (&result.$field, member.$field) {
+                (None, value) => result.$field = value,
+                (Some(a), Some(b)) if *a != b => return Err(conflict($keyword)),
+                (Some(_), _) => {}
+            }
+            )*
+        };
+    }
+    same!(
+        schema, "$schema";
+        id, "$id";
+        anchor, "$anchor";
+        dynamic_anchor, "$dynamicAnchor";
+        r#ref, "$ref";
+        dynamic_ref, "$dynamicRef";
+        any_of, "anyOf";
+        one_of, "oneOf";
+        not, "not";
+        r#if, "if";
+        then, "then";
+        r#else, "else";
+        items, "items";
+        contains, "contains";
+        additional_properties, "additionalProperties";
+        property_names, "propertyNames";
+        unevaluated_items, "unevaluatedItems";
+        unevaluated_properties, "unevaluatedProperties";
+        r#const, "const";
+        multiple_of, "multipleOf";
+        pattern, "pattern";
+        format, "format";
+        content_encoding, "contentEncoding";
+        content_media_type, "contentMediaType";
+        content_schema, "contentSchema";
+        discriminator, "discriminator";
+    );
+    if result.prefix_items.is_empty() {
+        result.prefix_items = member.prefix_items;
+    } else if !member.prefix_items.is_empty() && result.prefix_items != member.prefix_items {
+        return Err(conflict("prefixItems"));
+    }
+
+    /// Combine the maps, the same key must have an identical value.
+    macro_rules! combine {
+        ($( $result: expr, $member: expr, $keyword: expr );* $(;)?) => {
+            $(
+            for (name, value) in $member {
+                match $result.get(&name) {
+                    Some(existing) if *existing != value => {
+                        let location = format!("{}/{}", $keyword, escape(&name));
+                        return Err(conflict(&location));
+                    }
+                    Some(_) => {}
+                    None => {
+                        $result.insert(name, value);
+                    }
+                }
+            }
+            )*
+        };
+    }
+    if let Some(properties) = member.properties {
+        let result = result.properties.get_or_insert_with(IndexMap::new);
+        combine!(result, properties, "properties");
+    }
+    combine!(
+        result.pattern_properties, member.pattern_properties, "patternProperties";
+        result.dependent_schemas, member.dependent_schemas, "dependentSchemas";
+        result.dependent_required, member.dependent_required, "dependentRequired";
+        result.defs, member.defs, "$defs";
+    );
+    for name in member.required {
+        if !result.required.contains(&name) {
+            result.required.push(name);
+        }
+    }
+
+    if result.r#type.is_empty() {
+        result.r#type = member.r#type;
+    } else if !member.r#type.is_empty() {
+        result.r#type.retain(|t| member.r#type.contains(t));
+        if result.r#type.is_empty() {
+            return Err(conflict("type"));
+        }
+    }
+    if result.r#enum.is_empty() {
+        result.r#enum = member.r#enum;
+    } else if !member.r#enum.is_empty() {
+        result.r#enum.retain(|v| member.r#enum.contains(v));
+        if result.r#enum.is_empty() {
+            return Err(conflict("enum"));
+        }
+    }
+    Ok(())
+}
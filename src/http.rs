@@ -0,0 +1,26 @@
+//! Module with conversions to and from types of the `http` crate.
+
+use crate::{HttpMethod, Reference, Response, Responses};
+
+impl From<HttpMethod> for http::Method {
+    fn from(method: HttpMethod) -> http::Method {
+        match method {
+            HttpMethod::Get => http::Method::GET,
+            HttpMethod::Put => http::Method::PUT,
+            HttpMethod::Post => http::Method::POST,
+            HttpMethod::Delete => http::Method::DELETE,
+            HttpMethod::Options => http::Method::OPTIONS,
+            HttpMethod::Head => http::Method::HEAD,
+            HttpMethod::Patch => http::Method::PATCH,
+            HttpMethod::Trace => http::Method::TRACE,
+        }
+    }
+}
+
+impl Responses {
+    /// Returns the response for the HTTP `status`, see
+    /// [`Responses::for_status`].
+    pub fn get(&self, status: http::StatusCode) -> Option<&Reference<Response>> {
+        self.for_status(status.as_u16())
+    }
+}
@@ -0,0 +1,334 @@
+//! Module with validation of instances against a [`Schema`].
+
+use std::error::Error;
+use std::fmt;
+
+use indexmap::IndexMap;
+
+use crate::validate::escape;
+use crate::{is_supported_dialect, BoolOr, Schema, Type, Value};
+
+/// Error returned by [`Schema::validate_instance`].
+#[derive(Debug)]
+pub struct SchemaError {
+    /// Location of the invalid value in the instance as JSON pointer, e.g.
+    /// `/pets/0/name`. Empty for the root of the instance.
+    pub instance_path: String,
+    /// The schema keyword that failed validation, e.g. `minLength`.
+    pub keyword: &'static str,
+    /// Description of the error.
+    pub message: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid value at `{}` ({}): {}",
+            self.instance_path, self.keyword, self.message
+        )
+    }
+}
+
+impl Error for SchemaError {}
+
+impl Schema {
+    /// Validate `value` against this schema.
+    ///
+    /// Supports the following keywords: `type`, `enum`, `const`, `minimum`,
+    /// `maximum`, `exclusiveMinimum`, `exclusiveMaximum`, `multipleOf`,
+    /// `minLength`, `maxLength`, `pattern` (requires the `regex`
+    /// feature), `minItems`, `maxItems`, `prefixItems`, `items`, `required`,
+    /// `properties`, `patternProperties` (requires the `regex` feature),
+    /// `additionalProperties` and `not`. Other keywords, including `$ref`, are
+    /// ignored.
+    ///
+    /// Schemas are validated as JSON Schema Draft 2020-12. If the `$schema`
+    /// keyword refers to another dialect an error is returned for it, as the
+    /// results may be incorrect.
+    pub fn validate_instance(&self, value: &Value) -> Result<(), Vec<SchemaError>> {
+        let mut errors = Vec::new();
+        validate(self, value, &mut String::new(), &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn validate(schema: &Schema, value: &Value, path: &mut String, errors: &mut Vec<SchemaError>) {
+    let mut error = |keyword, message| {
+        errors.push(SchemaError {
+            instance_path: path.clone(),
+            keyword,
+            message,
+        });
+    };
+
+    if let Some(dialect) = &schema.schema {
+        if !is_supported_dialect(dialect) {
+            error("$schema", format!("unsupported dialect `{}`", dialect));
+        }
+    }
+    if !schema.r#type.is_empty() && !schema.r#type.iter().any(|t| is_type(value, t)) {
+        error("type", format!("expected type {:?}", schema.r#type));
+    }
+    if !schema.r#enum.is_empty() && !schema.r#enum.iter().any(|v| equal(v, value)) {
+        error("enum", "value not in enum".to_owned());
+    }
+    if let Some(expected) = &schema.r#const {
+        if !equal(expected, value) {
+            error("const", format!("expected {}", expected));
+        }
+    }
+    if let Some(not) = &schema.not {
+        if not.validate_instance(value).is_ok() {
+            error("not", "value matches the schema in `not`".to_owned());
+        }
+    }
+
+    match value {
+        Value::Number(n) => {
+            let n = n.as_f64();
+            if let Some(maximum) = schema.maximum {
+                if n > maximum {
+                    error("maximum", format!("{} is greater than {}", n, maximum));
+                }
+            }
+            if let Some(minimum) = schema.minimum {
+                if n < minimum {
+                    error("minimum", format!("{} is less than {}", n, minimum));
+                }
+            }
+            if let Some(maximum) = schema.exclusive_maximum {
+                if n >= maximum {
+                    error(
+                        "exclusiveMaximum",
+                        format!("{} is not less than {}", n, maximum),
+                    );
+                }
+            }
+            if let Some(minimum) = schema.exclusive_minimum {
+                if n <= minimum {
+                    error(
+                        "exclusiveMinimum",
+                        format!("{} is not greater than {}", n, minimum),
+                    );
+                }
+            }
+            if let Some(multiple_of) = schema.multiple_of {
+                if !is_multiple_of(n, multiple_of) {
+                    error(
+                        "multipleOf",
+                        format!("{} is not a multiple of {}", n, multiple_of),
+                    );
+                }
+            }
+        }
+        Value::String(s) => {
+            let length = s.chars().count();
+            if let Some(max_length) = schema.max_length {
+                if length > max_length {
+                    error(
+                        "maxLength",
+                        format!("longer than {} characters", max_length),
+                    );
+                }
+            }
+            if let Some(min_length) = schema.min_length {
+                if length < min_length {
+                    error(
+                        "minLength",
+                        format!("shorter than {} characters", min_length),
+                    );
+                }
+            }
+            #[cfg(feature = "regex")]
+            if let (Some(pattern), Some(regex)) = (&schema.pattern, schema.compiled_pattern()) {
+                match regex {
+                    Ok(regex) if regex.is_match(s) => {}
+                    Ok(_) => error("pattern", format!("doesn't match pattern `{}`", pattern)),
+                    Err(err) => error("pattern", format!("invalid pattern `{}`: {}", pattern, err)),
+                }
+            }
+        }
+        Value::Array(items) => validate_array(schema, items, path, errors),
+        Value::Object(object) => validate_object(schema, object, path, errors),
+        Value::Null | Value::Bool(_) => {}
+    }
+}
+
+/// Returns true if `n` is a multiple of `multiple_of`, allowing for floating
+/// point rounding errors, e.g. `0.3` is a multiple of `0.1`.
+fn is_multiple_of(n: f64, multiple_of: f64) -> bool {
+    if multiple_of <= 0.0 {
+        // Invalid per the specification, don't report every value.
+        return true;
+    }
+    let quotient = n / multiple_of;
+    (quotient - quotient.round()).abs() <= 1e-9 * quotient.abs().max(1.0)
+}
+
+fn validate_array(
+    schema: &Schema,
+    items: &[Value],
+    path: &mut String,
+    errors: &mut Vec<SchemaError>,
+) {
+    if let Some(max_items) = schema.max_items {
+        if items.len() > max_items {
+            errors.push(SchemaError {
+                instance_path: path.clone(),
+                keyword: "maxItems",
+                message: format!("more than {} items", max_items),
+            });
+        }
+    }
+    if let Some(min_items) = schema.min_items {
+        if items.len() < min_items {
+            errors.push(SchemaError {
+                instance_path: path.clone(),
+                keyword: "minItems",
+                message: format!("less than {} items", min_items),
+            });
+        }
+    }
+
+    for (i, item) in items.iter().enumerate() {
+        let item_schema = match schema.prefix_items.get(i) {
+            Some(item_schema) => item_schema,
+            None => match &schema.items {
+                Some(item_schema) => item_schema,
+                None => continue,
+            },
+        };
+        with_path(path, &i.to_string(), |path| {
+            validate(item_schema, item, path, errors)
+        });
+    }
+}
+
+fn validate_object(
+    schema: &Schema,
+    object: &IndexMap<String, Value>,
+    path: &mut String,
+    errors: &mut Vec<SchemaError>,
+) {
+    for required in &schema.required {
+        if !object.contains_key(required) {
+            errors.push(SchemaError {
+                instance_path: path.clone(),
+                keyword: "required",
+                message: format!("missing required property `{}`", required),
+            });
+        }
+    }
+
+    #[cfg(feature = "regex")]
+    let pattern_properties = compile_pattern_properties(schema, path, errors);
+
+    for (name, value) in object {
+        let mut matched = false;
+        if let Some(property_schema) = schema.properties.as_ref().and_then(|p| p.get(name)) {
+            matched = true;
+            with_path(path, name, |path| {
+                validate(property_schema, value, path, errors)
+            });
+        }
+        #[cfg(feature = "regex")]
+        for (regex, property_schema) in &pattern_properties {
+            if regex.is_match(name) {
+                matched = true;
+                with_path(path, name, |path| {
+                    validate(property_schema, value, path, errors)
+                });
+            }
+        }
+        // Without the `regex` feature we can't match the patterns, so we
+        // assume all properties match if there are any pattern properties.
+        #[cfg(not(feature = "regex"))]
+        if !schema.pattern_properties.is_empty() {
+            matched = true;
+        }
+        if matched {
+            continue;
+        }
+
+        match &schema.additional_properties {
+            Some(BoolOr::Item(property_schema)) => with_path(path, name, |path| {
+                validate(property_schema, value, path, errors)
+            }),
+            Some(BoolOr::Bool(false)) => errors.push(SchemaError {
+                instance_path: path.clone(),
+                keyword: "additionalProperties",
+                message: format!("additional property `{}` not allowed", name),
+            }),
+            Some(BoolOr::Bool(true)) | None => {}
+        }
+    }
+}
+
+/// Compiles the `patternProperties` of `schema`, sorted by pattern. Invalid
+/// patterns are reported in `errors` and never match any property.
+#[cfg(feature = "regex")]
+fn compile_pattern_properties<'s>(
+    schema: &'s Schema,
+    path: &str,
+    errors: &mut Vec<SchemaError>,
+) -> Vec<(regex::Regex, &'s Schema)> {
+    let mut pattern_properties: Vec<_> = schema.pattern_properties.iter().collect();
+    pattern_properties.sort_unstable_by_key(|(pattern, _)| *pattern);
+    pattern_properties
+        .into_iter()
+        .filter_map(
+            |(pattern, property_schema)| match regex::Regex::new(pattern) {
+                Ok(regex) => Some((regex, property_schema)),
+                Err(err) => {
+                    errors.push(SchemaError {
+                        instance_path: path.to_owned(),
+                        keyword: "patternProperties",
+                        message: format!("invalid pattern `{}`: {}", pattern, err),
+                    });
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+/// Call `f` with `token` appended to `path`.
+fn with_path<F: FnOnce(&mut String)>(path: &mut String, token: &str, f: F) {
+    let length = path.len();
+    path.push('/');
+    path.push_str(&escape(token));
+    f(path);
+    path.truncate(length);
+}
+
+fn is_type(value: &Value, r#type: &Type) -> bool {
+    match (r#type, value) {
+        (Type::Null, Value::Null)
+        | (Type::Boolean, Value::Bool(_))
+        | (Type::Object, Value::Object(_))
+        | (Type::Array, Value::Array(_))
+        | (Type::Number, Value::Number(_))
+        | (Type::String, Value::String(_)) => true,
+        (Type::Integer, Value::Number(n)) => n.is_i64() || n.is_u64() || n.as_f64().fract() == 0.0,
+        _ => false,
+    }
+}
+
+/// Compare two values, numbers are compared by their mathematical value.
+fn equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(l), Value::Number(r)) => l.as_f64() == r.as_f64(),
+        (Value::Array(l), Value::Array(r)) => {
+            l.len() == r.len() && l.iter().zip(r).all(|(l, r)| equal(l, r))
+        }
+        (Value::Object(l), Value::Object(r)) => {
+            l.len() == r.len() && l.iter().all(|(k, l)| r.get(k).is_some_and(|r| equal(l, r)))
+        }
+        (left, right) => left == right,
+    }
+}
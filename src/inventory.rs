@@ -0,0 +1,40 @@
+//! Module with the export of the operation inventory.
+
+use crate::{unique_operation_id, Spec};
+
+impl Spec {
+    /// Returns all operations in [`Spec::paths`] as a flat JSON array, for
+    /// use in tooling that only needs an inventory of the operations.
+    ///
+    /// Every entry is an object with the `operationId`, `method`, `path`,
+    /// `tags` and `deprecated` fields, e.g.
+    ///
+    /// ```json
+    /// { "operationId": "listPets", "method": "get", "path": "/pets", "tags": ["pets"], "deprecated": false }
+    /// ```
+    ///
+    /// Operations without an [`Operation::operation_id`] get an id derived
+    /// from the method and path, like [`Spec::ensure_operation_ids`] assigns.
+    ///
+    /// [`Operation::operation_id`]: crate::Operation::operation_id
+    pub fn operation_map_json(&self) -> serde_json::Value {
+        let mut used = self.operation_ids();
+        let operations = self
+            .operations()
+            .map(|(path, method, operation)| {
+                let id = match &operation.operation_id {
+                    Some(id) => id.clone(),
+                    None => unique_operation_id(&mut used, path, method),
+                };
+                serde_json::json!({
+                    "operationId": id,
+                    "method": method.as_str(),
+                    "path": path,
+                    "tags": operation.tags,
+                    "deprecated": operation.deprecated,
+                })
+            })
+            .collect();
+        serde_json::Value::Array(operations)
+    }
+}
@@ -4,8 +4,19 @@
 //!
 //! Currently this only supports [OpenAPI Specification v3.1.0]. Note that it
 //! can parse *some* OpenAPI v3.0.x specs, but not all as v3.1 contains breaking
-//! changes (e.g. `additionalProperties` previously accepted a boolean, which is
-//! no longer the case).
+//! changes.
+//!
+//! The following OpenAPI v3.0 constructs are normalised into their v3.1
+//! equivalent when parsing a [`Schema`]:
+//!  * `nullable: true` is converted into a `type` array containing `null`, see
+//!    [`Schema::normalize_nullable`].
+//!  * Boolean `exclusiveMaximum` and `exclusiveMinimum` are converted into the
+//!    numeric form, taking the value of `maximum` and `minimum` respectively.
+//!
+//! Parsing is enabled by the `json` and `yaml` features, which are enabled by
+//! default. Without any features (`default-features = false`) only the types
+//! modelling a specification are available, which don't depend on
+//! `serde_json` or `serde_yaml`.
 //!
 //! [OpenAPI Specification v3.1.0]: https://spec.openapis.org/oas/v3.1.0.html
 
@@ -14,20 +25,82 @@
 // JSON Schema             draft-bhutton-json-schema-00
 // JSON Schema Validation  draft-bhutton-json-schema-validation-00
 
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
+#[cfg(all(feature = "tokio", any(feature = "json", feature = "yaml")))]
+mod async_parse;
+#[cfg(any(feature = "json", feature = "yaml"))]
+mod bundle;
+mod content;
+mod diff;
+mod expression;
+mod extract;
+#[cfg(all(feature = "reqwest", any(feature = "json", feature = "yaml")))]
+mod fetch;
+mod flatten;
+#[cfg(feature = "http")]
+mod http;
+mod instance;
+#[cfg(feature = "json")]
+mod inventory;
+#[cfg(any(feature = "json", feature = "yaml"))]
+mod limit;
+mod merge;
+mod mock;
+#[cfg(any(feature = "json", feature = "yaml"))]
 mod parse;
+#[cfg(feature = "regex")]
+mod pattern;
+mod prune;
+mod resolve;
+#[cfg(feature = "json")]
+mod stream;
+mod template;
+#[cfg(feature = "url")]
+mod url;
+mod validate;
+mod value;
+mod visit;
+#[cfg(all(feature = "tokio", feature = "json"))]
+pub use async_parse::from_json_str_async;
+#[cfg(all(feature = "tokio", feature = "yaml"))]
+pub use async_parse::from_yaml_str_async;
+#[cfg(all(feature = "tokio", any(feature = "json", feature = "yaml")))]
+pub use async_parse::read_from_file_async;
+#[cfg(any(feature = "json", feature = "yaml"))]
+pub use bundle::BundleError;
+pub use content::select_media_type;
+pub use diff::{BreakingChange, Change, ChangeKind, SpecDiff};
+pub use expression::ExpressionError;
+#[cfg(all(feature = "reqwest", any(feature = "json", feature = "yaml")))]
+pub use fetch::{read_from_url, FetchError};
+pub use instance::SchemaError;
+pub use merge::{MergeConflict, MergePolicy};
 #[cfg(any(feature = "json", feature = "yaml"))]
-pub use parse::read_from_file;
+pub use parse::{read_from_file, read_from_reader, ParseError, ParseOptions};
 #[cfg(feature = "json")]
-pub use parse::read_from_json_file;
+pub use parse::{read_from_json_file, read_from_json_reader};
 #[cfg(feature = "yaml")]
-pub use parse::read_from_yaml_file;
+pub use parse::{read_from_yaml_file, read_from_yaml_reader};
+pub use resolve::{BrokenReference, BrokenReferenceKind, Component};
+#[cfg(feature = "json")]
+pub use stream::stream_json_paths;
+pub use template::{expand_path, PathError, ServerError};
+use validate::missing_security_scheme_field;
+pub use validate::ValidationError;
+use value::ValueDeserializer;
+pub use value::{Number, Value};
+pub use visit::{Visitor, VisitorMut};
 
 /// This is the root object of the OpenAPI document.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Spec {
     /// This string MUST be the version number of the OpenAPI Specification that
@@ -42,7 +115,7 @@ pub struct Spec {
     /// contained within this OAS document. This MUST be in the form of a URI.
     ///
     /// [Schema Objects]: Schema
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub json_schema_dialect: Option<String>,
     /// An array of Server Objects, which provide connectivity information to a
     /// target server. If the `servers` property is not provided, or is an empty
@@ -51,10 +124,10 @@ pub struct Spec {
     ///
     /// [Server Object]: Server
     /// [url]: Server::url
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<Server>,
     /// The available paths and operations for the API.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub paths: Paths,
     /// The incoming webhooks that MAY be received as part of this API and that
     /// the API consumer MAY choose to implement. Closely related to the
@@ -63,8 +136,8 @@ pub struct Spec {
     /// name is a unique string to refer to each webhook, while the (optionally
     /// referenced) Path Item Object describes a request that may be initiated
     /// by the API provider and the expected responses.
-    #[serde(default)]
-    pub webhooks: HashMap<String, PathItem>, // NOTE: `PathItem` includes all fields of `Reference`.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub webhooks: IndexMap<String, PathItem>, // NOTE: `PathItem` includes all fields of `Reference`.
     /// An element to hold various schemas for the document.
     #[serde(default)]
     pub components: Components,
@@ -74,7 +147,7 @@ pub struct Spec {
     /// be satisfied to authorize a request. Individual operations can override
     /// this definition. To make security optional, an empty security
     /// requirement (`{}`) can be included in the array.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub security: Vec<SecurityRequirement>,
     /// A list of tags used by the document with additional metadata. The order
     /// of the tags can be used to reflect on their order by the parsing tools.
@@ -83,86 +156,595 @@ pub struct Spec {
     /// tools' logic. Each tag name in the list MUST be unique.
     ///
     /// [Operation Object]: Operation
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<Tag>,
     /// Additional external documentation.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub external_docs: Option<ExternalDocument>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
+}
+
+impl Spec {
+    /// Returns the title of the API, i.e. [`Info::title`].
+    pub fn title(&self) -> &str {
+        &self.info.title
+    }
+
+    /// Returns the version of the API, i.e. [`Info::version`].
+    ///
+    /// Not to be confused with the version of the OpenAPI Specification, see
+    /// [`Spec::openapi_version`].
+    pub fn api_version(&self) -> &str {
+        &self.info.version
+    }
+
+    /// Returns the version of the OpenAPI Specification used, i.e.
+    /// [`Spec::openapi`].
+    pub const fn openapi_version(&self) -> Version {
+        self.openapi
+    }
+
+    /// Returns the default JSON Schema dialect of the [`Schema`]s in the
+    /// document, i.e. [`Spec::json_schema_dialect`].
+    ///
+    /// Defaults to the OpenAPI 3.1 base dialect,
+    /// `https://spec.openapis.org/oas/3.1/dialect/base`, which is JSON Schema
+    /// Draft 2020-12 with the OpenAPI vocabulary.
+    pub fn schema_dialect(&self) -> &str {
+        self.json_schema_dialect.as_deref().unwrap_or(OAS_DIALECT)
+    }
+
+    /// Returns all operations defined in [`Spec::paths`], along with their path
+    /// and method.
+    ///
+    /// Referenced path items are resolved, see [`PathItem::resolve`]. Note that
+    /// this doesn't include the operations in [`Spec::webhooks`].
+    pub fn operations(&self) -> impl Iterator<Item = (&str, HttpMethod, &Operation)> {
+        self.paths.iter().flat_map(move |(path, path_item)| {
+            path_item
+                .resolve(self)
+                .operations()
+                .map(move |(method, operation)| (&**path, method, operation))
+        })
+    }
+
+    /// Same as [`Spec::operations`], but returns mutable references.
+    ///
+    /// The operations of a path item in [`Components::path_items`] that is
+    /// referenced by multiple paths are only returned once, for the first
+    /// path.
+    pub fn operations_mut(&mut self) -> impl Iterator<Item = (&str, HttpMethod, &mut Operation)> {
+        let targets = self.resolved_path_item_names();
+        let mut referenced: HashMap<&str, &mut PathItem> = self
+            .components
+            .path_items
+            .iter_mut()
+            .map(|(name, path_item)| (&**name, path_item))
+            .collect();
+        let mut path_items = Vec::with_capacity(self.paths.len());
+        for ((path, path_item), target) in self.paths.iter_mut().zip(targets) {
+            let path_item = match target {
+                // Already returned for an earlier path.
+                Some(name) => match referenced.remove(&*name) {
+                    Some(path_item) => path_item,
+                    None => continue,
+                },
+                None => path_item,
+            };
+            path_items.push((&**path, path_item));
+        }
+        path_items.into_iter().flat_map(|(path, path_item)| {
+            path_item
+                .operations_mut()
+                .map(move |(method, operation)| (path, method, operation))
+        })
+    }
+
+    /// Returns the names of the path items in [`Components::path_items`] the
+    /// path items in [`Spec::paths`] resolve to, see [`PathItem::resolve`].
+    fn resolved_path_item_names(&self) -> Vec<Option<String>> {
+        self.paths
+            .values()
+            .map(|path_item| path_item.resolved_name(self).map(Cow::into_owned))
+            .collect()
+    }
+
+    /// Retain only the operations in [`Spec::paths`] for which `f` returns
+    /// `true`.
+    ///
+    /// Path items that no longer have any operations after removing
+    /// operations are removed as well. Components that were only used by the
+    /// removed operations are kept, use [`Spec::prune_unused_components`] to
+    /// remove them.
+    ///
+    /// Referenced path items are resolved, see [`PathItem::resolve`], and the
+    /// operations are removed from the path item in
+    /// [`Components::path_items`]. If that path item is referenced by
+    /// multiple paths `f` is only called for the first path, and all paths
+    /// referencing it are removed if it no longer has any operations.
+    pub fn retain_operations<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&str, HttpMethod, &Operation) -> bool,
+    {
+        let mut targets = self.resolved_path_item_names().into_iter();
+        let components = &mut self.components.path_items;
+        let mut retained: HashMap<String, bool> = HashMap::new();
+        self.paths.retain(|path, path_item| {
+            let name = match targets.next().flatten() {
+                Some(name) => name,
+                None => return retain_path_item_operations(path, path_item, &mut f),
+            };
+            if let Some(keep) = retained.get(&name) {
+                return *keep;
+            }
+            let keep = match components.get_mut(&name) {
+                Some(path_item) => retain_path_item_operations(path, path_item, &mut f),
+                None => true,
+            };
+            retained.insert(name, keep);
+            keep
+        });
+    }
+
+    /// Remove all operations in [`Spec::paths`] that have the specification
+    /// extension `name`, e.g. `x-internal`, unless its value is `false`.
+    ///
+    /// See [`Spec::retain_operations`].
+    pub fn remove_operations_with_extension(&mut self, name: &str) {
+        self.retain_operations(|_, _, operation| {
+            matches!(
+                operation.extensions.get(name),
+                None | Some(Value::Bool(false))
+            )
+        });
+    }
+
+    /// Returns the operation with [`Operation::operation_id`] `id`, along with
+    /// its path and method.
+    ///
+    /// This scans all operations, use [`Spec::build_operation_index`] when
+    /// doing many lookups.
+    pub fn operation_by_id(&self, id: &str) -> Option<(&str, HttpMethod, &Operation)> {
+        self.operations()
+            .find(|(_, _, operation)| operation.operation_id.as_deref() == Some(id))
+    }
+
+    /// Returns an index of all [`Operation::operation_id`]s to the path and
+    /// method of the operation.
+    ///
+    /// If an operation id is used multiple times (which is invalid) the first
+    /// operation is used.
+    pub fn build_operation_index(&self) -> HashMap<&str, (&str, HttpMethod)> {
+        let mut index = HashMap::new();
+        for (path, method, operation) in self.operations() {
+            if let Some(id) = operation.operation_id.as_deref() {
+                index.entry(id).or_insert((path, method));
+            }
+        }
+        index
+    }
+
+    /// Assign an [`Operation::operation_id`] to all operations in
+    /// [`Spec::paths`] that don't have one, returning the assigned ids along
+    /// with the path and method of the operation.
+    ///
+    /// The id is derived from the method and path, e.g. `getPetsId` for
+    /// `GET /pets/{id}`. If the id is already used a number is appended, e.g.
+    /// `getPetsId2`.
+    pub fn ensure_operation_ids(&mut self) -> HashMap<String, (String, HttpMethod)> {
+        let mut used = self.operation_ids();
+        let mut assigned = HashMap::new();
+        for (path, method, operation) in self.operations_mut() {
+            if operation.operation_id.is_some() {
+                continue;
+            }
+            let id = unique_operation_id(&mut used, path, method);
+            operation.operation_id = Some(id.clone());
+            assigned.insert(id, (path.to_owned(), method));
+        }
+        assigned
+    }
+
+    /// Returns all [`Operation::operation_id`]s, including the operations in
+    /// [`Spec::webhooks`], callbacks and the path items in the components.
+    fn operation_ids(&self) -> HashSet<String> {
+        struct Ids(HashSet<String>);
+
+        impl Visitor<'_> for Ids {
+            fn visit_operation(&mut self, _: &str, _: HttpMethod, operation: &Operation) {
+                self.0.extend(operation.operation_id.iter().cloned());
+            }
+        }
+
+        let mut ids = Ids(HashSet::new());
+        self.walk(&mut ids);
+        ids.0
+    }
+
+    /// Returns all tags used in [`Operation::tags`].
+    ///
+    /// This includes the operations in [`Spec::webhooks`], callbacks and the
+    /// path items in the components.
+    pub fn used_tags(&self) -> HashSet<&str> {
+        struct Tags<'s>(HashSet<&'s str>);
+
+        impl<'s> Visitor<'s> for Tags<'s> {
+            fn visit_operation(&mut self, _: &str, _: HttpMethod, operation: &'s Operation) {
+                self.0.extend(operation.tags.iter().map(String::as_str));
+            }
+        }
+
+        let mut tags = Tags(HashSet::new());
+        self.walk(&mut tags);
+        tags.0
+    }
+
+    /// Returns the tags used by operations that are not declared in
+    /// [`Spec::tags`], sorted by name.
+    ///
+    /// See [`Spec::used_tags`].
+    pub fn undeclared_tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = self
+            .used_tags()
+            .into_iter()
+            .filter(|name| !self.tags.iter().any(|tag| tag.name == *name))
+            .collect();
+        tags.sort_unstable();
+        tags
+    }
+
+    /// Returns the tags declared in [`Spec::tags`] that are not used by any
+    /// operation.
+    ///
+    /// See [`Spec::used_tags`].
+    pub fn unused_declared_tags(&self) -> Vec<&str> {
+        let used = self.used_tags();
+        self.tags
+            .iter()
+            .map(|tag| &*tag.name)
+            .filter(|name| !used.contains(name))
+            .collect()
+    }
+
+    /// Returns the default server, i.e. the first server in
+    /// [`Spec::servers`].
+    ///
+    /// If no servers are defined this returns a server with a [url] of `/`, as
+    /// required by the specification.
+    ///
+    /// [url]: Server::url
+    pub fn default_server(&self) -> Cow<'_, Server> {
+        match self.servers.first() {
+            Some(server) => Cow::Borrowed(server),
+            None => Cow::Owned(root_server()),
+        }
+    }
+
+    /// Returns the servers for the operation at `path` with `method`, or
+    /// `None` if the operation doesn't exist.
+    ///
+    /// The servers defined on the [operation] override the ones defined on the
+    /// [path item], which in turn override the ones in [`Spec::servers`]. If no
+    /// servers are defined at any level this returns a single server with a
+    /// [url] of `/`, see [`Spec::default_server`].
+    ///
+    /// [operation]: Operation::servers
+    /// [path item]: PathItem::servers
+    /// [url]: Server::url
+    pub fn servers_for(&self, path: &str, method: HttpMethod) -> Option<Cow<'_, [Server]>> {
+        let path_item = self.paths.get(path)?.resolve(self);
+        let operation = path_item.operation(method)?;
+        let servers = [&operation.servers, &path_item.servers, &self.servers]
+            .into_iter()
+            .find(|servers| !servers.is_empty());
+        Some(match servers {
+            Some(servers) => Cow::Borrowed(servers),
+            None => Cow::Owned(vec![root_server()]),
+        })
+    }
+
+    /// Returns the path of the [default server], e.g. `/v1` for
+    /// `https://example.com/v1/`, without a trailing slash.
+    ///
+    /// The variables in the server's URL are substituted with their default
+    /// value, see [`Server::resolve_url`].
+    ///
+    /// [default server]: Spec::default_server
+    pub fn base_path(&self) -> Result<String, ServerError> {
+        let url = self.default_server().resolve_url(&HashMap::new())?;
+        let path = match url.find("://") {
+            Some(start) => {
+                let host = &url[start + 3..];
+                host.find('/').map_or("", |end| &host[end..])
+            }
+            None => &*url,
+        };
+        let path = path.split(['?', '#']).next().unwrap_or("");
+        Ok(path.trim_end_matches('/').to_owned())
+    }
+
+    /// Returns all schemas defined in [`Components::schemas`].
+    ///
+    /// Note that the schemas are returned as is, see [`Spec::resolved_schema`]
+    /// to follow references.
+    pub fn schemas(&self) -> impl Iterator<Item = (&String, &Schema)> {
+        self.components.schemas.iter()
+    }
+
+    /// Returns the schema called `name` in [`Components::schemas`].
+    pub fn schema(&self, name: &str) -> Option<&Schema> {
+        self.components.schemas.get(name)
+    }
+
+    /// Returns the security scheme called `name` in
+    /// [`Components::security_schemes`], following references.
+    pub fn security_scheme(&self, name: &str) -> Option<&SecurityScheme> {
+        self.components.security_schemes.get(name)?.resolve(self)
+    }
+}
+
+/// Returns the server used when no servers are defined, with a url of `/`.
+fn root_server() -> Server {
+    Server {
+        url: "/".to_owned(),
+        description: None,
+        variables: HashMap::new(),
+        extensions: HashMap::new(),
+    }
+}
+
+/// The default JSON Schema dialect, see [`Spec::schema_dialect`].
+const OAS_DIALECT: &str = "https://spec.openapis.org/oas/3.1/dialect/base";
+
+/// Returns `true` if `dialect` is a JSON Schema dialect supported by this
+/// crate, i.e. JSON Schema Draft 2020-12, with or without the OpenAPI
+/// vocabulary.
+fn is_supported_dialect(dialect: &str) -> bool {
+    matches!(
+        dialect.trim_end_matches('#'),
+        OAS_DIALECT | "https://json-schema.org/draft/2020-12/schema"
+    )
+}
+
+/// Retain only the operations of `path_item` for which `f` returns `true`,
+/// returns `false` if all operations were removed, see
+/// [`Spec::retain_operations`].
+fn retain_path_item_operations<F>(path: &str, path_item: &mut PathItem, f: &mut F) -> bool
+where
+    F: FnMut(&str, HttpMethod, &Operation) -> bool,
+{
+    let remove: Vec<HttpMethod> = path_item
+        .operations()
+        .filter(|(method, operation)| !f(path, *method, operation))
+        .map(|(method, _)| method)
+        .collect();
+    for method in &remove {
+        *path_item.operation_slot(*method) = None;
+    }
+    remove.is_empty() || path_item.operations().next().is_some()
+}
+
+/// Returns a unique operation id for `method` and `path`, see
+/// [`operation_id`], and adds it to `used`.
+fn unique_operation_id(used: &mut HashSet<String>, path: &str, method: HttpMethod) -> String {
+    let base = operation_id(path, method);
+    let mut id = base.clone();
+    let mut n = 2;
+    while used.contains(&id) {
+        id = format!("{}{}", base, n);
+        n += 1;
+    }
+    used.insert(id.clone());
+    id
+}
+
+/// Returns the operation id for `method` and `path`, e.g. `getPetsId` for
+/// `GET /pets/{id}`.
+fn operation_id(path: &str, method: HttpMethod) -> String {
+    let mut id = method.as_str().to_owned();
+    for word in path.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            id.push(first.to_ascii_uppercase());
+            id.extend(chars);
+        }
+    }
+    id
 }
 
 /// The OpenAPI Specification version.
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+///
+/// All patch versions of 3.1 (e.g. `3.1.1`) are parsed as
+/// [`Version::OpenApi3_1`], keeping the patch version so that it's
+/// serialised as it was parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Version {
     /// Version `3.0.0`.
-    #[serde(rename = "3.0.0")]
     OpenApi3_0,
     /// Version `3.0.1`.
-    #[serde(rename = "3.0.1")]
     OpenApi3_0_1,
     /// Version `3.0.2`.
-    #[serde(rename = "3.0.2")]
     OpenApi3_0_2,
     /// Version `3.0.3`.
-    #[serde(rename = "3.0.3")]
     OpenApi3_0_3,
-    /// Version `3.1.0`.
-    #[serde(rename = "3.1.0")]
-    OpenApi3_1,
+    /// Version `3.1.x`, with the patch version, e.g. `OpenApi3_1(1)` for
+    /// `3.1.1`.
+    OpenApi3_1(u32),
 }
 
+impl Version {
+    /// Returns the version number, e.g. `3.1.0`.
+    pub fn as_str(&self) -> Cow<'static, str> {
+        match self {
+            Version::OpenApi3_0 => Cow::Borrowed("3.0.0"),
+            Version::OpenApi3_0_1 => Cow::Borrowed("3.0.1"),
+            Version::OpenApi3_0_2 => Cow::Borrowed("3.0.2"),
+            Version::OpenApi3_0_3 => Cow::Borrowed("3.0.3"),
+            Version::OpenApi3_1(0) => Cow::Borrowed("3.1.0"),
+            Version::OpenApi3_1(patch) => Cow::Owned(format!("3.1.{}", patch)),
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.as_str())
+    }
+}
+
+/// Parses the version, accepting all patch versions of 3.1, e.g. `3.1.1`.
+impl FromStr for Version {
+    type Err = ParseVersionError;
+
+    fn from_str(version: &str) -> Result<Version, ParseVersionError> {
+        match version {
+            "3.0.0" => Ok(Version::OpenApi3_0),
+            "3.0.1" => Ok(Version::OpenApi3_0_1),
+            "3.0.2" => Ok(Version::OpenApi3_0_2),
+            "3.0.3" => Ok(Version::OpenApi3_0_3),
+            _ => match version.strip_prefix("3.1.") {
+                // `u32::from_str` also accepts a leading `+`.
+                Some(patch) if patch.bytes().all(|b| b.is_ascii_digit()) => patch
+                    .parse()
+                    .map(Version::OpenApi3_1)
+                    .map_err(|_| ParseVersionError {
+                        version: version.to_owned(),
+                    }),
+                _ => Err(ParseVersionError {
+                    version: version.to_owned(),
+                }),
+            },
+        }
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = ParseVersionError;
+
+    fn try_from(version: &str) -> Result<Version, ParseVersionError> {
+        version.parse()
+    }
+}
+
+impl Serialize for Version {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D>(deserializer: D) -> Result<Version, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct VersionVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for VersionVisitor {
+            type Value = Version;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an OpenAPI version")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(VersionVisitor)
+    }
+}
+
+/// Error returned when parsing an unsupported [`Version`].
+#[derive(Debug)]
+pub struct ParseVersionError {
+    version: String,
+}
+
+impl fmt::Display for ParseVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unsupported OpenAPI version `{}`, supported versions are 3.0.0, 3.0.1, 3.0.2, 3.0.3 and 3.1.x",
+            self.version
+        )
+    }
+}
+
+impl std::error::Error for ParseVersionError {}
+
 /// The object provides metadata about the API.
 ///
 /// The metadata MAY be used by the clients if needed, and MAY be presented in
 /// editing or documentation generation tools for convenience.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Info {
     /// The title of the API.
     pub title: String,
     /// A short summary of the API.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
     /// A description of the API. [CommonMark syntax] MAY be used for rich text
     /// representation.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// A URL to the Terms of Service for the API. This MUST be in the form of a
     /// URL.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub terms_of_service: Option<String>,
     /// The contact information for the exposed API.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub contact: Option<Contact>,
     /// The license information for the exposed API.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub license: Option<License>,
     /// The version of the OpenAPI document (which is distinct from the OpenAPI
     /// Specification version or the API implementation version).
     pub version: String,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// Contact information for the exposed API.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Contact {
     /// The identifying name of the contact person/organization.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// The URL pointing to the contact information. This MUST be in the form of
     /// a URL.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
     /// The email address of the contact person/organization. This MUST be in
     /// the form of an email address.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// License information for the exposed API.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct License {
     /// The license name used for the API.
@@ -171,16 +753,21 @@ pub struct License {
     /// mutually exclusive of the `url` field.
     ///
     /// [SPDX]: (https://spdx.org/spdx-specification-21-web-version#h.jxpfx0ykyb60)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub identifier: Option<String>,
     /// A URL to the license used for the API. This MUST be in the form of a
     /// URL. The `url` field is mutually exclusive of the `identifier` field.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// An object representing a Server.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Server {
     /// A URL to the target host. This URL supports Server Variables and MAY be
@@ -192,23 +779,28 @@ pub struct Server {
     /// [CommonMark syntax] MAY be used for rich text representation.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// A map between a variable name and its value. The value is used for
     /// substitution in the server's URL template.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub variables: HashMap<String, ServerVariable>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// An object representing a Server Variable for server URL template
 /// substitution.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ServerVariable {
     /// An enumeration of string values to be used if the substitution options
     /// are from a limited set. The array MUST NOT be empty.
-    #[serde(default)]
-    pub r#enum: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#enum: Option<Vec<String>>,
     /// The default value to use for substitution, which SHALL be sent if an
     /// alternate value is _not_ supplied. Note this behavior is different than
     /// the [Schema Object's](#schemaObject) treatment of default values,
@@ -220,8 +812,13 @@ pub struct ServerVariable {
     /// be used for rich text representation.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// Holds a set of reusable objects for different aspects of the OAS.
@@ -229,59 +826,64 @@ pub struct ServerVariable {
 /// All objects defined within the components object will have no effect on the
 /// API unless they are explicitly referenced from properties outside the
 /// components object.
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Components {
     /// An object to hold reusable [Schema Objects].
     ///
     /// [Schema Objects]: Schema
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub schemas: HashMap<String, Schema>,
     /// An object to hold reusable [Response Objects].
     ///
     /// [Response Objects]: Response
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub responses: HashMap<String, Reference<Response>>,
     /// An object to hold reusable [Parameter Objects].
     ///
     /// [Parameter Objects]: Parameter
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub parameters: HashMap<String, Reference<Parameter>>,
     /// An object to hold reusable [Example Objects].
     ///
     /// [Example Objects]: Example
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub examples: HashMap<String, Reference<Example>>,
     /// An object to hold reusable [Request Body Objects].
     ///
     /// [Request Body Objects]: RequestBody
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub request_bodies: HashMap<String, Reference<RequestBody>>,
     /// An object to hold reusable [Header Objects].
     ///
     /// [Header Objects]: Header
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub headers: HashMap<String, Reference<Header>>,
     /// An object to hold reusable [Security Scheme Objects].
     ///
     /// [Security Scheme Objects]: SecurityScheme
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub security_schemes: HashMap<String, Reference<SecurityScheme>>,
     /// An object to hold reusable [Link Objects].
     ///
     /// [Link Objects]: Link
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub links: HashMap<String, Reference<Link>>,
     /// An object to hold reusable [Callback Objects].
     ///
     /// [Callback Objects]: Callback
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub callbacks: HashMap<String, Reference<Callback>>,
     /// An object to hold reusable [Path Item Object].
     ///
     /// [Path Item Objects]: PathItem
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub path_items: HashMap<String, PathItem>, // NOTE: `PathItem` includes all fields of `Reference`.
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// Holds the relative paths to the individual endpoints and their operations.
@@ -299,15 +901,17 @@ pub struct Components {
 /// templated names MUST NOT exist as they are identical. In case of ambiguous
 /// matching, it's up to the tooling to decide which one to use.
 ///
+/// The order of the paths is preserved.
+///
 /// [Server Object]: Server
-pub type Paths = HashMap<String, PathItem>;
+pub type Paths = IndexMap<String, PathItem>;
 
 /// Describes the operations available on a single path.
 ///
 /// A Path Item MAY be empty, due to ACL constraints. The path itself is still
 /// exposed to the documentation viewer but they will not know which operations
 /// and parameters are available.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct PathItem {
     /// Allows for a referenced definition of this path item. The referenced
@@ -317,44 +921,44 @@ pub struct PathItem {
     /// References.
     ///
     /// [Path Item Object]: PathItem
-    #[serde(rename = "$ref", default)]
+    #[serde(rename = "$ref", default, skip_serializing_if = "Option::is_none")]
     pub r#ref: Option<String>,
     /// An optional, string summary, intended to apply to all operations in this
     /// path.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
     /// An optional, string description, intended to apply to all operations in
     /// this path. [CommonMark syntax] MAY be used for rich text representation.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// A definition of a GET operation on this path.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub get: Option<Operation>,
     /// A definition of a PUT operation on this path.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub put: Option<Operation>,
     /// A definition of a POST operation on this path.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub post: Option<Operation>,
     /// A definition of a DELETE operation on this path.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub delete: Option<Operation>,
     /// A definition of a OPTIONS operation on this path.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub options: Option<Operation>,
     /// A definition of a HEAD operation on this path.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub head: Option<Operation>,
     /// A definition of a PATCH operation on this path.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub patch: Option<Operation>,
     /// A definition of a TRACE operation on this path.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub trace: Option<Operation>,
     /// An alternative `server` array to service all operations in this path.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<Server>,
     /// A list of parameters that are applicable for all the operations
     /// described under this path. These parameters can be overridden at the
@@ -367,36 +971,212 @@ pub struct PathItem {
     /// [name]: Parameter::name
     /// [location]: Parameter::in
     /// [OpenAPI Object's components/parameters]: Components::parameters
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub parameters: Vec<Reference<Parameter>>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
+}
+
+impl PathItem {
+    /// Returns the operation for `method`, if defined.
+    pub fn operation(&self, method: HttpMethod) -> Option<&Operation> {
+        match method {
+            HttpMethod::Get => self.get.as_ref(),
+            HttpMethod::Put => self.put.as_ref(),
+            HttpMethod::Post => self.post.as_ref(),
+            HttpMethod::Delete => self.delete.as_ref(),
+            HttpMethod::Options => self.options.as_ref(),
+            HttpMethod::Head => self.head.as_ref(),
+            HttpMethod::Patch => self.patch.as_ref(),
+            HttpMethod::Trace => self.trace.as_ref(),
+        }
+    }
+
+    /// Same as [`PathItem::operation`], but returns a mutable reference.
+    pub fn operation_mut(&mut self, method: HttpMethod) -> Option<&mut Operation> {
+        self.operation_slot(method).as_mut()
+    }
+
+    fn operation_slot(&mut self, method: HttpMethod) -> &mut Option<Operation> {
+        match method {
+            HttpMethod::Get => &mut self.get,
+            HttpMethod::Put => &mut self.put,
+            HttpMethod::Post => &mut self.post,
+            HttpMethod::Delete => &mut self.delete,
+            HttpMethod::Options => &mut self.options,
+            HttpMethod::Head => &mut self.head,
+            HttpMethod::Patch => &mut self.patch,
+            HttpMethod::Trace => &mut self.trace,
+        }
+    }
+
+    /// Returns all operations defined in the path item, along with their
+    /// method.
+    pub fn operations(&self) -> impl Iterator<Item = (HttpMethod, &Operation)> {
+        [
+            (HttpMethod::Get, &self.get),
+            (HttpMethod::Put, &self.put),
+            (HttpMethod::Post, &self.post),
+            (HttpMethod::Delete, &self.delete),
+            (HttpMethod::Options, &self.options),
+            (HttpMethod::Head, &self.head),
+            (HttpMethod::Patch, &self.patch),
+            (HttpMethod::Trace, &self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, operation)| operation.as_ref().map(|op| (method, op)))
+    }
+
+    /// Same as [`PathItem::operations`], but returns mutable references.
+    pub fn operations_mut(&mut self) -> impl Iterator<Item = (HttpMethod, &mut Operation)> {
+        [
+            (HttpMethod::Get, &mut self.get),
+            (HttpMethod::Put, &mut self.put),
+            (HttpMethod::Post, &mut self.post),
+            (HttpMethod::Delete, &mut self.delete),
+            (HttpMethod::Options, &mut self.options),
+            (HttpMethod::Head, &mut self.head),
+            (HttpMethod::Patch, &mut self.patch),
+            (HttpMethod::Trace, &mut self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, operation)| operation.as_mut().map(|op| (method, op)))
+    }
+}
+
+/// HTTP method of an [`Operation`] in a [`PathItem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpMethod {
+    /// GET method.
+    Get,
+    /// PUT method.
+    Put,
+    /// POST method.
+    Post,
+    /// DELETE method.
+    Delete,
+    /// OPTIONS method.
+    Options,
+    /// HEAD method.
+    Head,
+    /// PATCH method.
+    Patch,
+    /// TRACE method.
+    Trace,
+}
+
+impl HttpMethod {
+    /// All HTTP methods supported by [`PathItem`].
+    pub const ALL: [HttpMethod; 8] = [
+        HttpMethod::Get,
+        HttpMethod::Put,
+        HttpMethod::Post,
+        HttpMethod::Delete,
+        HttpMethod::Options,
+        HttpMethod::Head,
+        HttpMethod::Patch,
+        HttpMethod::Trace,
+    ];
+
+    /// Returns the name of the method as used in the [`PathItem`], e.g. `get`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            HttpMethod::Get => "get",
+            HttpMethod::Put => "put",
+            HttpMethod::Post => "post",
+            HttpMethod::Delete => "delete",
+            HttpMethod::Options => "options",
+            HttpMethod::Head => "head",
+            HttpMethod::Patch => "patch",
+            HttpMethod::Trace => "trace",
+        }
+    }
+}
+
+impl fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parses the method ignoring case, e.g. both `GET` and `get`.
+impl FromStr for HttpMethod {
+    type Err = ParseEnumError;
+
+    fn from_str(method: &str) -> Result<HttpMethod, ParseEnumError> {
+        HttpMethod::ALL
+            .into_iter()
+            .find(|m| m.as_str().eq_ignore_ascii_case(method))
+            .ok_or_else(|| ParseEnumError::new("HTTP method", method))
+    }
+}
+
+/// Error returned by the [`FromStr`] implementations of [`HttpMethod`],
+/// [`Type`] and [`Format`].
+#[derive(Debug)]
+pub struct ParseEnumError {
+    kind: &'static str,
+    value: String,
+}
+
+impl ParseEnumError {
+    fn new(kind: &'static str, value: &str) -> ParseEnumError {
+        ParseEnumError {
+            kind,
+            value: value.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown {} `{}`", self.kind, self.value)
+    }
+}
+
+impl std::error::Error for ParseEnumError {}
+
+/// Deserialise `T` from the string `value`, using the same names as in the
+/// document.
+fn from_name<'a, T: Deserialize<'a>>(
+    kind: &'static str,
+    value: &'a str,
+) -> Result<T, ParseEnumError> {
+    use serde::de::value::{Error, StrDeserializer};
+    T::deserialize(StrDeserializer::<Error>::new(value))
+        .map_err(|_| ParseEnumError::new(kind, value))
 }
 
 /// Describes a single API operation on a path.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Operation {
     /// A list of tags for API documentation control. Tags can be used for
     /// logical grouping of operations by resources or any other qualifier.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
     /// A short summary of what the operation does.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
     /// A verbose explanation of the operation behavior. [CommonMark syntax] MAY
     /// be used for rich text representation.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Additional external documentation for this operation.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub external_docs: Option<ExternalDocument>,
     /// Unique string used to identify the operation. The id MUST be unique
     /// among all operations described in the API. The operationId value is
     /// **case-sensitive**. Tools and libraries MAY use the operationId to
     /// uniquely identify an operation, therefore, it is RECOMMENDED to follow
     /// common programming naming conventions.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub operation_id: Option<String>,
     /// A list of parameters that are applicable for this operation. If a
     /// parameter is already defined at the [Path Item], the new definition will
@@ -411,7 +1191,7 @@ pub struct Operation {
     /// [location]: Parameter::in
     /// [Reference Object]: Reference
     /// [OpenAPI Object's components/parameters]: Components::parameters
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub parameters: Vec<Reference<Parameter>>,
     /// The request body applicable for this operation. The `request_body` is
     /// fully supported in HTTP methods where the HTTP 1.1 specification
@@ -424,11 +1204,11 @@ pub struct Operation {
     /// [GET]: https://tools.ietf.org/html/rfc7231#section-4.3.1
     /// [HEAD]: https://tools.ietf.org/html/rfc7231#section-4.3.2
     /// [DELETE]: https://tools.ietf.org/html/rfc7231#section-4.3.5
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub request_body: Option<Reference<RequestBody>>,
     /// The list of possible responses as they are returned from executing this
     /// operation.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub responses: Option<Responses>,
     /// A map of possible out-of band callbacks related to the parent operation.
     /// The key is a unique identifier for the Callback Object. Each value in
@@ -436,7 +1216,7 @@ pub struct Operation {
     /// initiated by the API provider and the expected responses.
     ///
     /// [Callback Object]: Callback
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub callbacks: HashMap<String, Reference<Callback>>,
     /// Declares this operation to be deprecated. Consumers SHOULD refrain from
     /// usage of the declared operation. Default value is `false`.
@@ -451,7 +1231,7 @@ pub struct Operation {
     /// top-level security declaration, an empty array can be used.
     ///
     /// [`security`]: Spec::security
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub security: Vec<SecurityRequirement>,
     /// An alternative `server` array to service this operation. If an
     /// alternative `server` object is specified at the [Path Item Object] or
@@ -459,22 +1239,82 @@ pub struct Operation {
     ///
     /// [Path Item Object]: PathItem
     /// [Root]: Spec
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<Server>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
+}
+
+impl Operation {
+    /// Returns the media types accepted for the request body, sorted by name.
+    ///
+    /// The [`request_body`] is resolved using `spec`. Returns an empty vector
+    /// if the operation has no request body or it can't be resolved.
+    ///
+    /// [`request_body`]: Operation::request_body
+    pub fn request_content_types<'a>(&'a self, spec: &'a Spec) -> Vec<&'a str> {
+        let request_body = self.request_body.as_ref().and_then(|b| b.resolve(spec));
+        content_types(request_body.map(|b| &b.content))
+    }
+
+    /// Returns `true` if the request body is required.
+    ///
+    /// The [`request_body`] is resolved using `spec`. Returns `false` if the
+    /// operation has no request body or it can't be resolved.
+    ///
+    /// [`request_body`]: Operation::request_body
+    pub fn body_required(&self, spec: &Spec) -> bool {
+        self.request_body
+            .as_ref()
+            .and_then(|b| b.resolve(spec))
+            .is_some_and(|b| b.required)
+    }
+
+    /// Returns the media types of the response for the HTTP `status`, sorted
+    /// by name.
+    ///
+    /// The response is looked up using [`Responses::for_status`] and resolved
+    /// using `spec`. Returns an empty vector if there is no such response.
+    pub fn response_content_types<'a>(&'a self, spec: &'a Spec, status: u16) -> Vec<&'a str> {
+        let response = self
+            .responses
+            .as_ref()
+            .and_then(|r| r.for_status(status))
+            .and_then(|r| r.resolve(spec));
+        content_types(response.map(|r| &r.content))
+    }
+}
+
+/// Returns the sorted keys of `content`.
+fn content_types(content: Option<&HashMap<String, MediaType>>) -> Vec<&str> {
+    let mut content_types: Vec<&str> = content
+        .into_iter()
+        .flat_map(|c| c.keys().map(String::as_str))
+        .collect();
+    content_types.sort_unstable();
+    content_types
 }
 
 /// Allows referencing an external resource for extended documentation.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExternalDocument {
     /// A description of the target documentation. [CommonMark syntax] MAY be
     /// used for rich text representation.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// The URL for the target documentation. This MUST be in the form of a URL.
     pub url: String,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// Describes a single operation parameter.
@@ -483,7 +1323,7 @@ pub struct ExternalDocument {
 ///
 /// [name]: Parameter::name
 /// [location]: Parameter::in
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Parameter {
     /// The name of the parameter. Parameter names are *case sensitive*.
@@ -507,7 +1347,7 @@ pub struct Parameter {
     /// use. [CommonMark syntax] MAY be used for rich text representation.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Determines whether this parameter is mandatory. If the [parameter
     /// location] is `"path"`, this property is **REQUIRED** and its value MUST
@@ -539,7 +1379,7 @@ pub struct Parameter {
     ///  * for `path` - `simple`
     ///  * for `header` - `simple`
     ///  * for `cookie` - `form`
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub style: Option<ParameterStyle>,
     /// When this is true, parameter values of type `array` or `object` generate
     /// separate parameters for each value of the array or key-value pair of the
@@ -559,7 +1399,7 @@ pub struct Parameter {
     #[serde(default)]
     pub allow_reserved: bool,
     /// The schema defining the type used for the parameter.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub schema: Option<Schema>,
     /// Example of the parameter's potential value. The example SHOULD match the
     /// specified schema and encoding properties if present. The `example` field
@@ -569,25 +1409,30 @@ pub struct Parameter {
     /// examples of media types that cannot naturally be represented in JSON or
     /// YAML, a string value can contain the example with escaping where
     /// necessary.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub example: Option<Any>,
     /// Examples of the parameter's potential value. Each example SHOULD contain
     /// a value in the correct format as specified in the parameter encoding.
     /// The `examples` field is mutually exclusive of the `example` field.
     /// Furthermore, if referencing a `schema` that contains an example, the
     /// `examples` value SHALL _override_ the example provided by the schema.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub examples: HashMap<String, Reference<Example>>,
     /// A map containing the representations for the parameter. The key is the
     /// media type and the value describes it. The map MUST only contain one
     /// entry.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub content: HashMap<String, MediaType>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// There are four possible parameter locations specified by the
 /// [`Parameter::in`] field.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub enum ParameterLocation {
     /// Used together with Path Templating, where the parameter value is
@@ -597,6 +1442,9 @@ pub enum ParameterLocation {
     Path,
     /// Parameters that are appended to the URL. For example, in
     /// `/items?id=###`, the query parameter is `id`.
+    ///
+    /// This is the default location.
+    #[default]
     Query,
     /// Custom headers that are expected as part of the request. Note that
     /// [RFC7230] states header names are case insensitive.
@@ -610,7 +1458,7 @@ pub enum ParameterLocation {
 /// Parameter style.
 ///
 /// See [`Parameter::style`].
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ParameterStyle {
     /// Path-style parameters defined by [RFC6570].
@@ -664,14 +1512,14 @@ pub enum ParameterStyle {
 }
 
 /// Describes a single request body.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct RequestBody {
     /// A brief description of the request body. This could contain examples of
     /// use. [CommonMark syntax] MAY be used for rich text representation.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// The content of the request body. The key is a media type or [media type
     /// range] and the value describes it. For requests that match multiple
@@ -684,40 +1532,50 @@ pub struct RequestBody {
     /// `false`.
     #[serde(default)]
     pub required: bool,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// Each Media Type Object provides schema and examples for the media type
 /// identified by its key.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct MediaType {
     /// The schema defining the content of the request, response, or parameter.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub schema: Option<Schema>,
     /// Example of the media type. The example object SHOULD be in the correct
     /// format as specified by the media type. The `example` field is mutually
     /// exclusive of the `examples` field. Furthermore, if referencing a
     /// `schema` which contains an example, the `example` value SHALL _override_
     /// the example provided by the schema.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub example: Option<Any>,
     /// Examples of the media type. Each example object SHOULD match the media
     /// type and specified schema if present. The `examples` field is mutually
     /// exclusive of the `example` field. Furthermore, if referencing a `schema`
     /// which contains an example, the `examples` value SHALL _override_ the
     /// example provided by the schema.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub examples: HashMap<String, Reference<Example>>,
     /// A map between a property name and its encoding information. The key,
     /// being the property name, MUST exist in the schema as a property. The
     /// encoding object SHALL only apply to `requestBody` objects when the media
     /// type is `multipart` or `application/x-www-form-urlencoded`.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub encoding: HashMap<String, Encoding>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// A single encoding definition applied to a single schema property.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Encoding {
     /// The Content-Type for encoding a specific property. Default value depends
@@ -729,13 +1587,13 @@ pub struct Encoding {
     /// The value can be a specific media type (e.g. `application/json`), a
     /// wildcard media type (e.g. `image/*`), or a comma-separated list of the
     /// two types.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub content_type: Option<String>,
     /// A map allowing additional information to be provided as headers, for
     /// example `Content-Disposition`. `Content-Type` is described separately
     /// and SHALL be ignored in this section. This property SHALL be ignored if
     /// the request body media type is not a `multipart`.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub headers: HashMap<String, Reference<Header>>,
     /// Describes how a specific property value will be serialized depending on
     /// its type. See [Parameter Object] for details on the [`style`] property.
@@ -748,7 +1606,7 @@ pub struct Encoding {
     /// [Parameter Object]: Parameter
     /// [`style`]: Parameter::style
     /// [`content_type`]: Encoding::content_type
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub style: Option<ParameterStyle>,
     /// When this is true, property values of type `array` or `object` generate
     /// separate parameters for each value of the array, or key-value-pair of
@@ -775,6 +1633,11 @@ pub struct Encoding {
     /// [`content_type`]: Encoding::content_type
     #[serde(default)]
     pub allow_reserved: bool,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// A container for the expected responses of an operation.
@@ -792,12 +1655,12 @@ pub struct Encoding {
 /// The `Responses Object` MUST contain at least one response code, and if only
 /// one response code is provided it SHOULD be the response for a successful
 /// operation call.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Responses {
     /// The documentation of responses other than the ones declared for specific
     /// HTTP response codes. Use this field to cover undeclared responses.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default: Option<Reference<Response>>,
     /// Any HTTP status code can be used as the property name, but only one
     /// property per code, to describe the expected response for that HTTP
@@ -811,13 +1674,91 @@ pub struct Responses {
     /// and `5XX`. If a response is defined using an explicit code, the explicit
     /// code definition takes precedence over the range definition for that
     /// code.
-    #[serde(flatten, default)]
-    pub response: HashMap<String, Reference<Response>>,
+    #[serde(flatten, default, deserialize_with = "without_extensions::deserialize")]
+    pub response: IndexMap<String, Reference<Response>>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(default, flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
+}
+
+impl Responses {
+    /// Returns the response for the HTTP status `code`.
+    ///
+    /// This first looks for the exact `code` (e.g. `404`), then the matching
+    /// range (e.g. `4XX`) and finally falls back to [`Responses::default`].
+    pub fn for_status(&self, code: u16) -> Option<&Reference<Response>> {
+        self.response
+            .get(&*code.to_string())
+            .or_else(|| self.response.get(&*format!("{}XX", code / 100)))
+            .or(self.default.as_ref())
+    }
+
+    /// Returns all responses in [`Responses::response`] with their parsed
+    /// status code.
+    ///
+    /// Keys that are not a valid status code or range are skipped.
+    pub fn status_codes(&self) -> impl Iterator<Item = (StatusCode, &Reference<Response>)> {
+        self.response
+            .iter()
+            .filter_map(|(key, response)| Some((StatusCode::parse(key)?, response)))
+    }
+
+    /// Returns all responses, including [`Responses::default`] using the key
+    /// `default`.
+    ///
+    /// The responses in [`Responses::response`] are returned first, in the
+    /// order they're defined in, followed by the default response.
+    pub fn iter_all(&self) -> impl Iterator<Item = (&str, &Reference<Response>)> {
+        self.response
+            .iter()
+            .map(|(key, response)| (&**key, response))
+            .chain(self.default.iter().map(|response| ("default", response)))
+    }
+}
+
+/// Key in [`Responses::response`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusCode {
+    /// Single status code, e.g. `404`.
+    Code(u16),
+    /// Range of status codes, e.g. `4` for `4XX`, which represents all status
+    /// codes between 400 and 499.
+    Range(u8),
+}
+
+impl StatusCode {
+    /// Parse a key of [`Responses::response`], e.g. `200` or `2XX`.
+    pub fn parse(key: &str) -> Option<StatusCode> {
+        match key.as_bytes() {
+            [class @ b'1'..=b'5', b'X', b'X'] => Some(StatusCode::Range(class - b'0')),
+            [b'1'..=b'5', b'0'..=b'9', b'0'..=b'9'] => key.parse().ok().map(StatusCode::Code),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if `code` is matched by this status code.
+    pub fn matches(self, code: u16) -> bool {
+        match self {
+            StatusCode::Code(c) => c == code,
+            StatusCode::Range(class) => code / 100 == u16::from(class),
+        }
+    }
+}
+
+impl fmt::Display for StatusCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StatusCode::Code(code) => write!(f, "{}", code),
+            StatusCode::Range(class) => write!(f, "{}XX", class),
+        }
+    }
 }
 
 /// Describes a single response from an API Operation, including design-time,
 /// static `links` to operations based on the response.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     /// A description of the response. [CommonMark syntax] MAY be used for rich
@@ -830,7 +1771,7 @@ pub struct Response {
     /// `"Content-Type"`, it SHALL be ignored.
     ///
     /// [RFC7230]: https://tools.ietf.org/html/rfc7230#page-22
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub headers: HashMap<String, Reference<Header>>,
     /// A map containing descriptions of potential response payloads. The key is
     /// a media type or [media type range] and the value describes it. For
@@ -838,15 +1779,32 @@ pub struct Response {
     /// applicable. e.g. `text/plain` overrides `text/*`.
     ///
     /// [media type range]: https://tools.ietf.org/html/rfc7231#appendix-D
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub content: HashMap<String, MediaType>,
     /// A map of operations links that can be followed from the response. The
     /// key of the map is a short name for the link, following the naming
     /// constraints of the names for [Component Objects].
     ///
     /// [Component Objects]: Components
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub links: HashMap<String, Reference<Link>>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
+}
+
+impl Response {
+    /// Returns the header called `name`, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&Reference<Header>> {
+        self.headers.get(name).or_else(|| {
+            self.headers
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, header)| header)
+        })
+    }
 }
 
 /// Callback Object.
@@ -863,13 +1821,18 @@ pub struct Response {
 ///
 /// [Path Item Object]: PathItem
 /// [`webhooks`]: Spec::webhooks
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Callback {
     /// A Path Item Object, or a reference to one, used to define a callback
     /// request and expected responses.
-    #[serde(default)]
-    pub expressions: HashMap<String, PathItem>, // NOTE: `PathItem` includes all fields of `Reference`.
+    #[serde(flatten, default, deserialize_with = "without_extensions::deserialize")]
+    pub expressions: IndexMap<String, PathItem>, // NOTE: `PathItem` includes all fields of `Reference`.
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(default, flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// Example Object.
@@ -878,30 +1841,35 @@ pub struct Callback {
 /// schema of its associated value. Tooling implementations MAY choose to
 /// validate compatibility automatically, and reject the example value(s) if
 /// incompatible.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Example {
     /// Short description for the example.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
     /// Long description for the example. [CommonMark syntax] MAY be used for
     /// rich text representation.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Embedded literal example. The `value` field and `externalValue` field
     /// are mutually exclusive. To represent examples of media types that cannot
     /// naturally represented in JSON or YAML, use a string value to contain the
     /// example, escaping where necessary.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub value: Option<Any>,
     /// A URI that points to the literal example. This provides the capability
     /// to reference examples that cannot easily be included in JSON or YAML
     /// documents. The `value` field and `externalValue` field are mutually
     /// exclusive.
-    #[serde(default)]
-    pub external_value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_value: Option<String>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// The Link object represents a possible design-time link for a response.
@@ -925,7 +1893,7 @@ pub struct Example {
 /// external references.
 ///
 /// [runtime expression]: RuntimeExpression
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Link {
     /// A relative or absolute URI reference to an OAS operation. This field is
@@ -934,12 +1902,12 @@ pub struct Link {
     /// an existing [Operation Object] in the OpenAPI definition.
     ///
     /// [Operation Object]: Operation
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub operation_ref: Option<String>,
     /// The name of an _existing_, resolvable OAS operation, as defined with a
     /// unique `operationId`. This field is mutually exclusive of the
     /// `operationRef` field.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub operation_id: Option<String>,
     /// A map representing parameters to pass to an operation as specified with
     /// `operationId` or identified via `operationRef`. The key is the parameter
@@ -950,23 +1918,28 @@ pub struct Link {
     /// path.id).
     ///
     /// [parameter location]: Parameter::in
-    #[serde(default)]
-    pub parameters: HashMap<String, RuntimeExpression>, // TODO: or `Any`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub parameters: HashMap<String, ExpressionOrValue>,
     /// A literal value or [{expression}] to use as a request body when calling
     /// the target operation.
     ///
     /// [{expression}]: RuntimeExpression
-    #[serde(default)]
-    pub request_body: Option<RuntimeExpression>, // TODO: or `Any`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<ExpressionOrValue>,
     /// A description of the link. [CommonMark syntax] MAY be used for rich text
     /// representation.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// A server object to be used by the target operation.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub server: Option<Server>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// Runtime expressions allow defining values based on information that will
@@ -1005,7 +1978,53 @@ pub struct Link {
 /// [RFC6901]: https://tools.ietf.org/html/rfc6901
 /// [RFC7159]: https://tools.ietf.org/html/rfc7159#section-7
 /// [RFC7230]: https://tools.ietf.org/html/rfc7230#section-3.2.6
-pub type RuntimeExpression = String;
+///
+/// The expression can be parsed using its [`FromStr`] implementation and
+/// formatted using its [`Display`] implementation.
+///
+/// [`FromStr`]: std::str::FromStr
+/// [`Display`]: std::fmt::Display
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuntimeExpression {
+    /// `$url`, the full URL of the request.
+    Url,
+    /// `$method`, the HTTP method of the request.
+    Method,
+    /// `$statusCode`, the status code of the response.
+    StatusCode,
+    /// `$request.{source}`, a value from the request.
+    Request(ExpressionSource),
+    /// `$response.{source}`, a value from the response.
+    Response(ExpressionSource),
+}
+
+/// Either a [`RuntimeExpression`] or a constant value.
+///
+/// When deserializing, strings that are valid runtime expressions are parsed
+/// as [`ExpressionOrValue::Expression`], all other values (including other
+/// strings) are kept as [`ExpressionOrValue::Value`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionOrValue {
+    /// A runtime expression, e.g. `$response.body#/id`.
+    Expression(RuntimeExpression),
+    /// A constant value.
+    Value(Any),
+}
+
+/// Source of a [`RuntimeExpression`] in the request or response.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionSource {
+    /// `header.{token}`, the value of a header. Note that header names are
+    /// case-insensitive.
+    Header(String),
+    /// `query.{name}`, the value of a query parameter.
+    Query(String),
+    /// `path.{name}`, the value of a path parameter.
+    Path(String),
+    /// `body[#{json-pointer}]`, the body or part of it. The JSON pointer, if
+    /// any, is stored in its escaped form, e.g. `/pets/0/id`.
+    Body(Option<String>),
+}
 
 /// Header Object.
 ///
@@ -1020,14 +2039,14 @@ pub type RuntimeExpression = String;
 ///
 /// [Parameter Object]: Parameter
 /// [`style`]: Header::style
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Header {
     /// A brief description of the parameter. This could contain examples of
     /// use. [CommonMark syntax] MAY be used for rich text representation.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Determines whether this parameter is mandatory. If the [parameter
     /// location] is `"path"`, this property is **REQUIRED** and its value MUST
@@ -1045,10 +2064,10 @@ pub struct Header {
     /// type of the parameter value.
     ///
     /// Default value is `simple`.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub style: Option<HeaderStyle>,
     /// The schema defining the type used for the parameter.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub schema: Option<Schema>,
     /// Example of the parameter's potential value. The example SHOULD match the
     /// specified schema and encoding properties if present. The `example` field
@@ -1058,26 +2077,31 @@ pub struct Header {
     /// examples of media types that cannot naturally be represented in JSON or
     /// YAML, a string value can contain the example with escaping where
     /// necessary.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub example: Option<Any>,
     /// Examples of the parameter's potential value. Each example SHOULD contain
     /// a value in the correct format as specified in the parameter encoding.
     /// The `examples` field is mutually exclusive of the `example` field.
     /// Furthermore, if referencing a `schema` that contains an example, the
     /// `examples` value SHALL _override_ the example provided by the schema.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub examples: HashMap<String, Reference<Example>>,
     /// A map containing the representations for the parameter. The key is the
     /// media type and the value describes it. The map MUST only contain one
     /// entry.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub content: HashMap<String, MediaType>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// Header style.
 ///
 /// See [`Header::style`].
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum HeaderStyle {
     /// Simple style parameters defined by [RFC6570]. This option replaces
@@ -1097,7 +2121,7 @@ pub enum HeaderStyle {
 /// instances.
 ///
 /// [Operation Object]: Operation
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Tag {
     /// The name of the tag.
@@ -1106,11 +2130,16 @@ pub struct Tag {
     /// representation.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Additional external documentation for this tag.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub external_docs: Option<ExternalDocument>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// A simple object to allow referencing other components in the OpenAPI
@@ -1120,28 +2149,87 @@ pub struct Tag {
 /// location of the value being referenced.
 ///
 /// [RFC3986]: https://tools.ietf.org/html/rfc3986
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Reference<T> {
     /// The reference identifier. This MUST be in the form of a URI.
-    #[serde(rename = "$ref")]
-    r#ref: Option<String>,
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub r#ref: Option<String>,
     /// A short summary which by default SHOULD override that of the
     /// referenced component. If the referenced object-type does not allow a
     /// `summary` field, then this field has no effect.
-    #[serde(default)]
-    summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
     /// A description which by default SHOULD override that of the
     /// referenced component. [CommonMark syntax] MAY be used for rich text
     /// representation. If the referenced object-type does not allow a
     /// `description` field, then this field has no effect.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
-    description: Option<String>,
-    /// Object `T`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Object `T` if defined inline, `None` if this is a reference.
     #[serde(flatten)]
-    object: Option<T>,
+    pub object: Option<T>,
+}
+
+impl<T> Reference<T> {
+    /// Returns `true` if this is a reference to another object, i.e. `$ref` is
+    /// set, `false` if the object is defined inline.
+    pub fn is_reference(&self) -> bool {
+        self.r#ref.is_some()
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Reference<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Reference<T>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // NOTE: we can't use a struct with a flattened `T` as the `summary`
+        // and `description` fields would take the fields from `T`. Instead we
+        // buffer the value and check for `$ref`, deserialising `T` directly
+        // otherwise so that its errors are returned (an untagged enum would
+        // hide them).
+        #[derive(Deserialize)]
+        struct Ref {
+            #[serde(rename = "$ref")]
+            r#ref: String,
+            #[serde(default)]
+            summary: Option<String>,
+            #[serde(default)]
+            description: Option<String>,
+        }
+
+        let value = Value::deserialize(deserializer)?;
+        let is_reference = value
+            .as_object()
+            .is_some_and(|object| object.contains_key("$ref"));
+        let deserializer = ValueDeserializer::new(value);
+        if is_reference {
+            let Ref {
+                r#ref,
+                summary,
+                description,
+            } = Ref::deserialize(deserializer)?;
+            Ok(Reference {
+                r#ref: Some(r#ref),
+                summary,
+                description,
+                object: None,
+            })
+        } else {
+            Ok(Reference {
+                r#ref: None,
+                summary: None,
+                description: None,
+                object: Some(T::deserialize(deserializer)?),
+            })
+        }
+    }
 }
 
 /// The Schema Object allows the definition of input and output data types.
@@ -1162,8 +2250,8 @@ pub struct Reference<T> {
 /// [JSON Schema Specification Draft 2020-12]: https://tools.ietf.org/html/draft-bhutton-json-schema-00
 /// [JSON Schema Core]: https://tools.ietf.org/html/draft-bhutton-json-schema-00
 /// [JSON Schema Validation]: https://tools.ietf.org/html/draft-bhutton-json-schema-validation-00
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", remote = "Self")]
 pub struct Schema {
     // JSON Schema Section 8. The JSON Schema Core Vocabulary
     /// The `$schema` keyword is both used as a JSON Schema dialect identifier
@@ -1180,7 +2268,7 @@ pub struct Schema {
     /// support.
     ///
     /// [URI]: https://datatracker.ietf.org/doc/html/rfc3986
-    #[serde(rename = "$schema", default)]
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
     pub schema: Option<String>,
     /// The `$id` keyword identifies a schema resource with its canonical [RFC
     /// 6596] URI.
@@ -1190,8 +2278,21 @@ pub struct Schema {
     /// downloadable from its canonical URI.
     ///
     /// [RFC 6596]: https://datatracker.ietf.org/doc/html/rfc6596
-    #[serde(rename = "$id", default)]
+    #[serde(rename = "$id", default, skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
+    /// The `$anchor` keyword is used to create a plain name fragment that is
+    /// not tied to the structure of the JSON document, e.g. `#item` for
+    /// `"$anchor": "item"`.
+    #[serde(rename = "$anchor", default, skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<String>,
+    /// The `$dynamicAnchor` keyword creates a plain name fragment like
+    /// `$anchor`, which can additionally be targeted by `$dynamicRef`.
+    #[serde(
+        rename = "$dynamicAnchor",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub dynamic_anchor: Option<String>,
     /// The `$ref` keyword is an applicator that is used to reference a
     /// statically identified schema. Its results are the results of the
     /// referenced schema.
@@ -1201,8 +2302,32 @@ pub struct Schema {
     /// the schema to apply. This resolution is safe to perform on schema load,
     /// as the process of evaluating an instance cannot change how the reference
     /// resolves.
-    #[serde(rename = "$ref", default)]
+    #[serde(rename = "$ref", default, skip_serializing_if = "Option::is_none")]
     pub r#ref: Option<String>,
+    /// The `$dynamicRef` keyword is an applicator that allows for deferring the
+    /// full resolution until runtime, at which point it is resolved each time
+    /// it is encountered while evaluating an instance. It's resolved using the
+    /// `$dynamicAnchor`s in the dynamic scope.
+    ///
+    /// Note that this crate doesn't resolve `$dynamicRef`s.
+    #[serde(
+        rename = "$dynamicRef",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub dynamic_ref: Option<String>,
+    /// The `$defs` keyword reserves a location for schema authors to inline
+    /// re-usable JSON Schemas into a more general schema, e.g. referenced as
+    /// `#/components/schemas/Pet/$defs/Name`.
+    ///
+    /// The legacy `definitions` keyword is accepted as alias.
+    #[serde(
+        rename = "$defs",
+        alias = "definitions",
+        default,
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub defs: HashMap<String, Schema>,
     /// This keyword reserves a location for comments from schema authors to
     /// readers or maintainers of the schema.
     ///
@@ -1210,28 +2335,28 @@ pub struct Schema {
     /// editing schemas SHOULD support displaying and editing this keyword. The
     /// value of this keyword MAY be used in debug or error output which is
     /// intended for developers making use of schemas.
-    #[serde(rename = "$comment", default)]
+    #[serde(rename = "$comment", default, skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
 
     // JSON Schema Section 10.2.1. Keywords for Applying Subschemas With Logic
     /// An instance validates successfully against this keyword if it validates
     /// successfully against all schemas defined by this keyword's value.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub all_of: Option<Vec<Schema>>,
     /// An instance validates successfully against this keyword if it validates
     /// successfully against at least one schema defined by this keyword's
     /// value. Note that when annotations are being collected, all subschemas
     /// MUST be examined so that annotations are collected from each subschema
     /// that validates successfully.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub any_of: Option<Vec<Schema>>,
     /// An instance validates successfully against this keyword if it validates
     /// successfully against exactly one schema defined by this keyword's value.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub one_of: Option<Vec<Schema>>,
     /// An instance is valid against this keyword if it fails to validate
     /// successfully against the schema defined by this keyword.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub not: Option<Box<Schema>>,
 
     // JSON Schema Section 10.2.2. Keywords for Applying Subschemas
@@ -1247,7 +2372,7 @@ pub struct Schema {
     /// Instances that fail to validate against this keyword's subschema MUST
     /// also be valid against the subschema value of the `else` keyword, if
     /// present.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub r#if: Option<Box<Schema>>,
     /// When `if` is present, and the instance successfully validates against
     /// its subschema, then validation succeeds against this keyword if the
@@ -1257,7 +2382,7 @@ pub struct Schema {
     /// fails to validate against its subschema. Implementations MUST NOT
     /// evaluate the instance against this keyword, for either validation or
     /// annotation collection purposes, in such cases.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub then: Option<Box<Schema>>,
     /// When `if` is present, and the instance fails to validate against its
     /// subschema, then validation succeeds against this keyword if the instance
@@ -1267,7 +2392,7 @@ pub struct Schema {
     /// successfully validates against its subschema. Implementations MUST NOT
     /// evaluate the instance against this keyword, for either validation or
     /// annotation collection purposes, in such cases.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub r#else: Option<Box<Schema>>,
     /// This keyword specifies subschemas that are evaluated if the instance is
     /// an object and contains a certain property.
@@ -1277,7 +2402,7 @@ pub struct Schema {
     /// presence of the property.
     ///
     /// Omitting this keyword has the same behavior as an empty object.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub dependent_schemas: HashMap<String, Schema>,
 
     // JSON Schema Section 10.3.1. Keywords for Applying Subschemas to Arrays
@@ -1293,7 +2418,7 @@ pub struct Schema {
     /// `items` and `unevaluated_items`.
     ///
     /// Omitting this keyword has the same assertion behavior as an empty array.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub prefix_items: Vec<Schema>,
     /// This keyword applies its subschema to all instance elements at indexes
     /// greater than the length of the `prefix_iItems` array in the same schema
@@ -1313,7 +2438,7 @@ pub struct Schema {
     /// another way that produces the same effect, such as by directly checking
     /// for the presence and size of a `prefix_items` array. Implementations
     /// that do not support annotation collection MUST do so.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub items: Option<Box<Schema>>,
     /// An array instance is valid against `contains` if at least one of its
     /// elements is valid against the given schema. The subschema MUST be
@@ -1330,7 +2455,7 @@ pub struct Schema {
     /// subschema validates successfully when applied to every index of the
     /// instance. The annotation MUST be present if the instance array to which
     /// this keyword's schema applies is empty.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub contains: Option<Box<Schema>>,
 
     // JSON Schema Section 10.3.2. Keywords for Applying Subschemas to Objects
@@ -1343,8 +2468,8 @@ pub struct Schema {
     ///
     /// Omitting this keyword has the same assertion behavior as an empty
     /// object.
-    #[serde(default)]
-    pub properties: Option<HashMap<String, Schema>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<IndexMap<String, Schema>>,
     /// Each property name of this object SHOULD be a valid regular expression,
     /// according to the ECMA-262 regular expression dialect. Each property
     /// value of this object MUST be a valid JSON Schema.
@@ -1359,7 +2484,7 @@ pub struct Schema {
     ///
     /// Omitting this keyword has the same assertion behavior as an empty
     /// object.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub pattern_properties: HashMap<String, Schema>,
     /// The behavior of this keyword depends on the presence and annotation
     /// results of `properties` and `pattern_properties` within the same schema
@@ -1381,14 +2506,17 @@ pub struct Schema {
     /// the names in `properties` and the patterns in `pattern_properties`
     /// against the instance property set. Implementations that do not support
     /// annotation collection MUST do so.
-    #[serde(default)]
-    pub additional_properties: Option<Box<Schema>>,
+    ///
+    /// `additionalProperties: false`, disallowing all additional properties,
+    /// is stored as `Some(BoolOr::Bool(false))`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_properties: Option<BoolOr<Box<Schema>>>,
     /// If the instance is an object, this keyword validates if every property
     /// name in the instance validates against the provided schema. Note the
     /// property name that the schema is testing will always be a string.
     ///
     /// Omitting this keyword has the same behavior as an empty schema.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub property_names: Option<Box<Schema>>,
 
     // JSON Schema Section 11. A Vocabulary for Unevaluated Locations
@@ -1420,7 +2548,7 @@ pub struct Schema {
     ///
     /// Omitting this keyword has the same assertion behavior as an empty
     /// schema.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub unevaluated_items: Option<Box<Schema>>,
     /// The behavior of this keyword depends on the annotation results of
     /// adjacent keywords that apply to the instance location being validated.
@@ -1451,76 +2579,88 @@ pub struct Schema {
     ///
     /// Omitting this keyword has the same assertion behavior as an empty
     /// schema.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub unevaluated_properties: Option<Box<Schema>>,
 
     // JSON Schema Validation Section 6.1. Validation Keywords for Any Instance
     // Type
     /// Data type.
-    #[serde(with = "one_or_array", default)]
+    ///
+    /// An empty vector means there is no type constraint, and the keyword is
+    /// omitted when serialising. A single type is serialised as string,
+    /// multiple types as array.
+    #[serde(with = "one_or_array", default, skip_serializing_if = "Vec::is_empty")]
     pub r#type: Vec<Type>,
+    /// OpenAPI v3.0 `nullable` keyword, allowing `null` in addition to the
+    /// [`Schema::type`].
+    ///
+    /// This is normalised into `type` when parsing, see
+    /// [`Schema::normalize_nullable`], so it's only set for schemas
+    /// constructed manually.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub nullable: bool,
     /// Valid values for this schema.
-    #[serde(default)]
-    pub r#enum: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub r#enum: Vec<Any>,
     /// Use of this keyword is functionally equivalent to an [`enum`] with a
     /// single value.
     ///
     /// [`enum`]: Schema::enum
-    #[serde(default)]
-    pub r#const: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#const: Option<Any>,
 
     // JSON Schema Validation Section 6.2. Validation Keywords for Numeric
     // Instances (number and integer)
     /// A numeric instance is valid only if division by this keyword's value
     /// results in an integer.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub multiple_of: Option<f64>,
     /// If the instance is a number, then this keyword validates only if the
     /// instance is less than or exactly equal to `maximum`.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub maximum: Option<f64>,
     /// If the instance is a number, then the instance is valid only if it has a
     /// value strictly less than (not equal to) `exclusiveMaximum`.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub exclusive_maximum: Option<f64>,
     /// If the instance is a number, then this keyword validates only if the
     /// instance is greater than or exactly equal to `minimum`.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub minimum: Option<f64>,
     /// If the instance is a number, then the instance is valid only if it has a
     /// value strictly greater than (not equal to) `exclusiveMinimum`.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub exclusive_minimum: Option<f64>,
 
     // JSON Schema Validation Section 6.3. Validation Keywords for Strings
     /// A string instance is valid against this keyword if its length is less
     /// than, or equal to, the value of this keyword.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_length: Option<usize>,
     /// A string instance is valid against this keyword if its length is greater
     /// than, or equal to, the value of this keyword.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub min_length: Option<usize>,
     /// A string instance is considered valid if the regular expression matches
     /// the instance successfully. Recall: regular expressions are not
     /// implicitly anchored.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pattern: Option<String>,
 
     // JSON Schema Validation Section 6.4. Validation Keywords for Arrays
     /// An array instance is valid against `maxItems` if its size is less than,
     /// or equal to, the value of this keyword.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_items: Option<usize>,
     /// An array instance is valid against `minItems` if its size is greater
     /// than, or equal to, the value of this keyword.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub min_items: Option<usize>,
     /// If this keyword has boolean value false, the instance validates
     /// successfully. If it has boolean value true, the instance validates
     /// successfully if all of its elements are unique.
     #[serde(default)]
-    pub unique_ttems: bool,
+    pub unique_items: bool,
     /// If `contains` is not present within the same schema object, then this
     /// keyword has no effect.
     ///
@@ -1531,7 +2671,7 @@ pub struct Schema {
     /// `maxContains` value. The second way is if the annotation result is a
     /// boolean `true` and the instance array length is less than or equal to
     /// the `maxContains` value.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_contains: Option<usize>,
     /// If `contains` is not present within the same schema object, then this
     /// keyword has no effect.
@@ -1549,25 +2689,25 @@ pub struct Schema {
     /// `maxContains` causes `contains` to always pass validation.
     ///
     /// Omitting this keyword has the same behavior as a value of 1.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub min_contains: Option<usize>,
 
     // JSON Schema Validation Section 6.5. Validation Keywords for Objects
     /// An object instance is valid against `maxProperties` if its number of
     /// properties is less than, or equal to, the value of this keyword.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_properties: Option<usize>,
     /// An object instance is valid against `minProperties` if its number of
     /// properties is greater than, or equal to, the value of this keyword.
     ///
     /// Omitting this keyword has the same behavior as a value of 0.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub min_properties: Option<usize>,
     /// An object instance is valid against this keyword if every item in the
     /// array is the name of a property in the instance.
     ///
     /// Omitting this keyword has the same behavior as an empty array.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub required: Vec<String>,
     /// This keyword specifies properties that are required if a specific other
     /// property is present. Their requirement is dependent on the presence of
@@ -1578,7 +2718,7 @@ pub struct Schema {
     /// corresponding array is also the name of a property in the instance.
     ///
     /// Omitting this keyword has the same behavior as an empty object.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub dependent_required: HashMap<String, Vec<String>>,
 
     // JSON Schema Validation Section 7. Vocabularies for Semantic Content With
@@ -1587,7 +2727,7 @@ pub struct Schema {
     /// convey semantic information for a fixed subset of values which are
     /// accurately described by authoritative resources, be they RFCs or other
     /// external specifications.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub format: Option<FormatOrString>,
 
     // JSON Schema Validation Section 8. A Vocabulary for the Contents of
@@ -1612,7 +2752,7 @@ pub struct Schema {
     ///
     /// [RFC 4648]: https://datatracker.ietf.org/doc/html/rfc4648
     /// [RFC 2045]: https://datatracker.ietf.org/doc/html/rfc2045
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub content_encoding: Option<String>,
     /// If the instance is a string, this property indicates the media type of
     /// the contents of the string. If `content_encoding` is present, this
@@ -1622,7 +2762,7 @@ pub struct Schema {
     /// as defined by [RFC 2046].
     ///
     /// [RFC 2046]: https://datatracker.ietf.org/doc/html/rfc2046
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub content_media_type: Option<String>,
     /// If the instance is a string, and if `content_media_type` is present,
     /// this property contains a schema which describes the structure of the
@@ -1633,26 +2773,26 @@ pub struct Schema {
     ///
     /// The value of this property MUST be a valid JSON schema. It SHOULD be
     /// ignored if `content_media_type` is not present.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub content_schema: Option<Box<Schema>>,
 
     // JSON Schema Validation Section 9. A Vocabulary for Basic Meta-Data
     // Annotations
     /// A title will preferably be short explanation about the purpose of the
     /// instance described by this schema.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
     /// A description will provide explanation about the purpose of the instance
     /// described by this schema. [CommonMark syntax] MAY be used for rich text
     /// representation.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// This keyword can be used to supply a default JSON value associated with
     /// a particular schema. It is RECOMMENDED that a default value be valid
     /// against the associated schema.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default: Option<Any>,
     /// If `deprecated` has a value of boolean true, it indicates that
     /// applications SHOULD refrain from usage of the declared property. It MAY
@@ -1702,22 +2842,22 @@ pub struct Schema {
     /// Implementations MAY use the value(s) of `default`, if present, as an
     /// additional example. If `examples` is absent, `default` MAY still be used
     /// in this manner.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub examples: Vec<Any>,
 
     // OpenAPI spec extension.
     /// Adds support for polymorphism. The discriminator is an object name that
     /// is used to differentiate between other schemas which may satisfy the
     /// payload description.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub discriminator: Option<Discriminator>,
     /// This MAY be used only on properties schemas. It has no effect on root
     /// schemas. Adds additional metadata to describe the XML representation of
     /// this property.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub xml: Option<Xml>,
     /// Additional external documentation for this schema.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub external_docs: Option<ExternalDocument>,
     /// A free-form property to include an example of an instance for this
     /// schema. To represent examples that cannot be naturally represented in
@@ -1727,7 +2867,7 @@ pub struct Schema {
     /// **Deprecated:** The `example` property has been deprecated in favor of
     /// the JSON Schema `examples` keyword. Use of `example` is discouraged, and
     /// later versions of this specification may remove it.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub example: Option<Any>,
     /// Allows the schema to be extended. The value can be `null`/`None`, a
     /// primitive, an array or an object.
@@ -1735,10 +2875,243 @@ pub struct Schema {
     pub extensions: HashMap<String, Any>,
 }
 
+impl Serialize for Schema {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Schema::serialize(self, serializer)
+    }
+}
+
+impl Schema {
+    /// Returns the boolean schema `true`, which accepts all values.
+    ///
+    /// This is the same as an empty schema (`{}`).
+    pub fn accept_all() -> Schema {
+        Schema::default()
+    }
+
+    /// Returns the boolean schema `false`, which rejects all values.
+    ///
+    /// This is represented as `{"not": {}}`.
+    pub fn reject_all() -> Schema {
+        Schema {
+            not: Some(Box::new(Schema::accept_all())),
+            ..Schema::default()
+        }
+    }
+
+    /// Normalise the OpenAPI v3.0 [`Schema::nullable`] keyword by adding
+    /// [`Type::Null`] to [`Schema::type`] and removing the flag.
+    ///
+    /// If the schema has no `type` it already allows `null` and only the flag
+    /// is removed.
+    pub fn normalize_nullable(&mut self) {
+        if self.nullable && !self.r#type.is_empty() && !self.r#type.contains(&Type::Null) {
+            self.r#type.push(Type::Null);
+        }
+        self.nullable = false;
+    }
+
+    /// Returns `true` if the schema explicitly allows `null`, i.e. if
+    /// [`Schema::type`] contains [`Type::Null`] or [`Schema::nullable`] is set.
+    ///
+    /// Note that a schema without a `type` also allows `null`, but this returns
+    /// `false` for it.
+    pub fn is_nullable(&self) -> bool {
+        self.nullable || self.r#type.contains(&Type::Null)
+    }
+
+    /// Returns `true` if `property` is listed in [`Schema::required`].
+    pub fn is_required(&self, property: &str) -> bool {
+        self.required.iter().any(|p| p == property)
+    }
+
+    /// Returns the single non-null [`Schema::type`], e.g. [`Type::String`] for
+    /// `["string", "null"]`.
+    ///
+    /// Returns `None` if there is no type constraint or if multiple non-null
+    /// types are allowed.
+    pub fn primary_type(&self) -> Option<Type> {
+        let mut types = self.r#type.iter().filter(|t| **t != Type::Null);
+        match (types.next(), types.next()) {
+            (Some(r#type), None) => Some(r#type.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the schema of the encoded content of a string, i.e.
+    /// [`Schema::content_schema`].
+    ///
+    /// Returns `None` if [`Schema::content_media_type`] is not set, in which
+    /// case the content schema should be ignored.
+    pub fn content_schema(&self) -> Option<&Schema> {
+        self.content_media_type.as_ref()?;
+        self.content_schema.as_deref()
+    }
+}
+
+/// Also accepts boolean schemas, see [`Schema::accept_all`] and
+/// [`Schema::reject_all`], and OpenAPI v3.0 schemas, see the crate
+/// documentation for the constructs that are normalised.
+impl<'de> Deserialize<'de> for Schema {
+    fn deserialize<D>(deserializer: D) -> Result<Schema, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct SchemaVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for SchemaVisitor {
+            type Value = Schema;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a schema object or boolean")
+            }
+
+            fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(if v {
+                    Schema::accept_all()
+                } else {
+                    Schema::reject_all()
+                })
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                deserialize_compat(serde::de::value::MapAccessDeserializer::new(map))
+            }
+        }
+
+        deserializer.deserialize_any(SchemaVisitor)
+    }
+}
+
+/// Deserialize a schema object, normalising OpenAPI v3.0 constructs.
+fn deserialize_compat<'de, D>(deserializer: D) -> Result<Schema, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    /// Fields that changed between OpenAPI v3.0 and v3.1.
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Compat {
+        #[serde(default)]
+        nullable: bool,
+        #[serde(default)]
+        exclusive_maximum: Option<NumberOrBool>,
+        #[serde(default)]
+        exclusive_minimum: Option<NumberOrBool>,
+        #[serde(flatten, deserialize_with = "Schema::deserialize")]
+        schema: Schema,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrBool {
+        Number(f64),
+        Bool(bool),
+    }
+
+    let Compat {
+        nullable,
+        exclusive_maximum,
+        exclusive_minimum,
+        mut schema,
+    } = Compat::deserialize(deserializer)?;
+    schema.nullable = nullable;
+    schema.normalize_nullable();
+    schema.exclusive_maximum = match exclusive_maximum {
+        Some(NumberOrBool::Number(n)) => Some(n),
+        Some(NumberOrBool::Bool(true)) => schema.maximum.take(),
+        Some(NumberOrBool::Bool(false)) | None => None,
+    };
+    schema.exclusive_minimum = match exclusive_minimum {
+        Some(NumberOrBool::Number(n)) => Some(n),
+        Some(NumberOrBool::Bool(true)) => schema.minimum.take(),
+        Some(NumberOrBool::Bool(false)) | None => None,
+    };
+    Ok(schema)
+}
+
+mod extensions {
+    //! Deserialize function for the `extensions` fields.
+    //!
+    //! Only keeps the fields that start with `x-`.
+
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Deserializer};
+
+    use crate::Any;
+
+    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<String, Any>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut extensions = HashMap::<String, Any>::deserialize(deserializer)?;
+        extensions.retain(|name, _| name.starts_with("x-"));
+        Ok(extensions)
+    }
+}
+
+mod without_extensions {
+    //! Deserialize function for flattened maps next to an `extensions` field.
+    //!
+    //! Skips the fields that start with `x-`.
+
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use indexmap::IndexMap;
+    use serde::de::{IgnoredAny, MapAccess, Visitor};
+    use serde::{Deserialize, Deserializer};
+
+    pub(crate) fn deserialize<'de, D, T>(deserializer: D) -> Result<IndexMap<String, T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        struct MapVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for MapVisitor<T> {
+            type Value = IndexMap<String, T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut values = IndexMap::new();
+                while let Some(name) = map.next_key::<String>()? {
+                    if name.starts_with("x-") {
+                        map.next_value::<IgnoredAny>()?;
+                    } else {
+                        values.insert(name, map.next_value()?);
+                    }
+                }
+                Ok(values)
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+}
+
 mod one_or_array {
     //! Deserialize and Serialize functions for [`Schema::type`].
     //!
-    //! Accepts either 1 type (string) or an array of types (strings).
+    //! Accepts either 1 type (string) or an array of types (strings). When
+    //! serialising exactly 1 type is written as string, any other number of
+    //! types as array.
     //!
     //! [`Schema::type`]: crate::Schema::type
 
@@ -1767,9 +3140,7 @@ mod one_or_array {
                 E: Error,
             {
                 let r#type = Type::deserialize(v.into_deserializer())?;
-                let mut types = Vec::with_capacity(1);
-                types.push(r#type);
-                Ok(types)
+                Ok(vec![r#type])
             }
 
             fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
@@ -1798,10 +3169,23 @@ mod one_or_array {
     }
 }
 
+/// Either a boolean or `T`.
+///
+/// Used for keywords that accept a boolean schema, where `true` accepts all
+/// values and `false` rejects all values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BoolOr<T> {
+    /// Boolean.
+    Bool(bool),
+    /// Item `T`.
+    Item(T),
+}
+
 /// Data type defined by [JSON Schema Validation Section 6.1.1].
 ///
 /// [JSON Schema Validation Section 6.1.1]: https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-6.1.1
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Type {
     Null,
@@ -1813,19 +3197,92 @@ pub enum Type {
     Integer,
 }
 
+impl Type {
+    /// Returns the name of the type, e.g. `string`.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Type::Null => "null",
+            Type::Boolean => "boolean",
+            Type::Object => "object",
+            Type::Array => "array",
+            Type::Number => "number",
+            Type::String => "string",
+            Type::Integer => "integer",
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Type {
+    type Err = ParseEnumError;
+
+    fn from_str(r#type: &str) -> Result<Type, ParseEnumError> {
+        from_name("type", r#type)
+    }
+}
+
 /// Either a known [`Format`] or falls back to a string.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum FormatOrString {
     Format(Format),
     Other(String),
 }
 
+impl FormatOrString {
+    /// Returns the format if it's a known [`Format`].
+    pub fn as_known(&self) -> Option<&Format> {
+        match self {
+            FormatOrString::Format(format) => Some(format),
+            FormatOrString::Other(_) => None,
+        }
+    }
+
+    /// Returns `true` if this is a known numeric format, see
+    /// [`Format::is_numeric`].
+    pub fn is_numeric(&self) -> bool {
+        self.as_known().is_some_and(Format::is_numeric)
+    }
+
+    /// Returns `true` if this is a known format for strings, see
+    /// [`Format::is_string_like`].
+    pub fn is_string_like(&self) -> bool {
+        self.as_known().is_some_and(Format::is_string_like)
+    }
+}
+
+impl fmt::Display for FormatOrString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FormatOrString::Format(format) => format.fmt(f),
+            FormatOrString::Other(format) => f.write_str(format),
+        }
+    }
+}
+
+/// Falls back to [`FormatOrString::Other`] for unknown formats, thus never
+/// fails.
+impl FromStr for FormatOrString {
+    type Err = Infallible;
+
+    fn from_str(format: &str) -> Result<FormatOrString, Infallible> {
+        Ok(match format.parse() {
+            Ok(format) => FormatOrString::Format(format),
+            Err(_) => FormatOrString::Other(format.to_owned()),
+        })
+    }
+}
+
 /// Data format defined by [JSON Schema Validation Section 7.3] and extended by
 /// the OpenAPI spec.
 ///
 /// [JSON Schema Validation Section 7.3]: https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-7.3
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Format {
     // JSON Schema Validation Section 7.3.1. Dates, Times, and Duration
@@ -1975,6 +3432,85 @@ pub enum Format {
     Password,
 }
 
+impl Format {
+    /// Returns `true` for the numeric formats: `int32`, `int64`, `float` and
+    /// `double`.
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Format::Int32 | Format::Int64 | Format::Float | Format::Double
+        )
+    }
+
+    /// Returns `true` for the formats that apply to strings, i.e. all
+    /// non-numeric formats.
+    pub fn is_string_like(&self) -> bool {
+        !self.is_numeric()
+    }
+
+    /// Returns the idiomatic Rust type for values of this format, e.g. `i32`
+    /// for `int32` and `Vec<u8>` for `binary`. All other string formats
+    /// return `String`.
+    pub fn rust_type(&self) -> &'static str {
+        match self {
+            Format::Int32 => "i32",
+            Format::Int64 => "i64",
+            Format::Float => "f32",
+            Format::Double => "f64",
+            Format::Binary => "Vec<u8>",
+            _ => "String",
+        }
+    }
+
+    /// Returns the name of the format, e.g. `date-time`.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Format::DateTime => "date-time",
+            Format::Date => "date",
+            Format::Time => "time",
+            Format::Duration => "duration",
+            Format::Email => "email",
+            Format::IdnEmail => "idn-email",
+            Format::Hostname => "hostname",
+            Format::IdnHostname => "idn-hostname",
+            Format::Ipv4 => "ipv4",
+            Format::Ipv6 => "ipv6",
+            Format::Uri => "uri",
+            Format::UriReference => "uri-reference",
+            Format::Iri => "iri",
+            Format::IriReference => "iri-reference",
+            Format::Uuid => "uuid",
+            Format::UriTemplate => "uri-template",
+            Format::JsonPointer => "json-pointer",
+            Format::RelativeJsonPointer => "relative-json-pointer",
+            Format::Regex => "regex",
+            Format::Binary => "binary",
+            Format::Ip => "ip",
+            Format::Int32 => "int32",
+            Format::Int64 => "int64",
+            Format::Float => "float",
+            Format::Double => "double",
+            Format::Password => "password",
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Also accepts the aliases, e.g. `url` for [`Format::Uri`]. See
+/// [`FormatOrString`] to fall back to a string for unknown formats.
+impl FromStr for Format {
+    type Err = ParseEnumError;
+
+    fn from_str(format: &str) -> Result<Format, ParseEnumError> {
+        from_name("format", format)
+    }
+}
+
 /// Discriminator Object.
 ///
 /// When request bodies or response payloads may be one of a number of different
@@ -1987,7 +3523,7 @@ pub enum Format {
 ///
 /// The discriminator object is legal only when using one of the composite
 /// keywords `oneOf`, `anyOf`, `allOf`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Discriminator {
     /// The name of the property in the payload that will hold the discriminator
@@ -1995,8 +3531,13 @@ pub struct Discriminator {
     pub property_name: String,
     /// An object to hold mappings between payload values and schema names or
     /// references.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub mapping: HashMap<String, String>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// XML Object.
@@ -2005,7 +3546,7 @@ pub struct Discriminator {
 ///
 /// When using arrays, XML element names are *not* inferred (for singular/plural
 /// forms) and the `name` property SHOULD be used to add that information.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Xml {
     /// Replaces the name of the element/attribute used for the described schema
@@ -2014,16 +3555,16 @@ pub struct Xml {
     /// being `array` (outside the `items`), it will affect the wrapping element
     /// and only if `wrapped` is `true`. If `wrapped` is `false`, it will be
     /// ignored.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// The URI of the namespace definition. This MUST be in the form of an
     /// absolute URI.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub namespace: Option<String>,
     /// The prefix to be used for the [`name`].
     ///
     /// [`name`]: Xml::name
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub prefix: Option<String>,
     /// Declares whether the property definition translates to an attribute
     /// instead of an element. Default value is `false`.
@@ -2036,6 +3577,11 @@ pub struct Xml {
     /// `items`).
     #[serde(default)]
     pub wrapped: bool,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// Security Scheme Object.
@@ -2053,7 +3599,7 @@ pub struct Xml {
 /// [RFC6749]: https://tools.ietf.org/html/rfc6749
 /// [OpenID Connect Discovery]: https://tools.ietf.org/html/draft-ietf-oauth-discovery-06
 /// [OAuth 2.0 Security Best Current Practice]: https://tools.ietf.org/html/draft-ietf-oauth-security-topics
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SecurityScheme {
     /// The type of the security scheme.
@@ -2062,17 +3608,17 @@ pub struct SecurityScheme {
     /// rich text representation.
     ///
     /// [CommonMark syntax]: https://spec.commonmark.org
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// The name of the header, query or cookie parameter to be used.
     ///
     /// Required for [`SecuritySchemeType::ApiKey`].
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// The location of the API key.
     ///
     /// Required for [`SecuritySchemeType::ApiKey`].
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub r#in: Option<SecuritySchemeIn>,
     /// The name of the HTTP Authorization scheme to be used in the
     /// [Authorization header as defined in RFC7235]. The values used SHOULD be
@@ -2082,32 +3628,37 @@ pub struct SecurityScheme {
     ///
     /// [Authorization header as defined in RFC7235]: https://tools.ietf.org/html/rfc7235#section-5.1
     /// [IANA Authentication Scheme registry]: https://www.iana.org/assignments/http-authschemes/http-authschemes.xhtml
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub scheme: Option<String>,
     /// A hint to the client to identify how the bearer token is formatted.
     /// Bearer tokens are usually generated by an authorization server, so this
     /// information is primarily for documentation purposes.
     ///
     /// Required for [`SecuritySchemeType::Http`] (`"bearer"`).
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bearer_format: Option<String>,
     /// An object containing configuration information for the flow types
     /// supported.
     ///
     /// Required for [`SecuritySchemeType::Oauth2`].
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub flows: Option<OauthFlows>,
     /// OpenId Connect URL to discover OAuth2 configuration values. This MUST be
     /// in the form of a URL. The OpenID Connect standard requires the use of
     /// TLS.
     ///
     /// Required for [`SecuritySchemeType::OpenIdConnect`].
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub open_id_connect_url: Option<String>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// [`SecurityScheme::type`].
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum SecuritySchemeType {
     #[serde(rename = "apiKey")]
     ApiKey,
@@ -2122,7 +3673,7 @@ pub enum SecuritySchemeType {
 }
 
 /// [`SecurityScheme::in`].
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum SecuritySchemeIn {
     #[serde(rename = "query")]
     Query,
@@ -2132,28 +3683,170 @@ pub enum SecuritySchemeIn {
     Cookie,
 }
 
+/// Typed alternative to the type specific fields of [`SecurityScheme`].
+///
+/// Unlike [`SecurityScheme`] only the fields that apply to the type of the
+/// security scheme can be set, and the required fields are not optional. Use
+/// the [`From`] and [`TryFrom`] implementations to convert from and to a
+/// [`SecurityScheme`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum SecuritySchemeKind {
+    /// [`SecuritySchemeType::ApiKey`].
+    #[serde(rename = "apiKey")]
+    ApiKey {
+        /// See [`SecurityScheme::name`].
+        name: String,
+        /// See [`SecurityScheme::in`].
+        r#in: SecuritySchemeIn,
+    },
+    /// [`SecuritySchemeType::Http`].
+    #[serde(rename = "http", rename_all = "camelCase")]
+    Http {
+        /// See [`SecurityScheme::scheme`].
+        scheme: String,
+        /// See [`SecurityScheme::bearer_format`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bearer_format: Option<String>,
+    },
+    /// [`SecuritySchemeType::MutualTls`].
+    #[serde(rename = "mutualTLS")]
+    MutualTls,
+    /// [`SecuritySchemeType::Oauth2`].
+    #[serde(rename = "oauth2")]
+    Oauth2 {
+        /// See [`SecurityScheme::flows`].
+        flows: Box<OauthFlows>,
+    },
+    /// [`SecuritySchemeType::OpenIdConnect`].
+    #[serde(rename = "openIdConnect")]
+    OpenIdConnect {
+        /// See [`SecurityScheme::open_id_connect_url`].
+        #[serde(rename = "openIdConnectUrl")]
+        url: String,
+    },
+}
+
+/// Creates a security scheme without a description or extensions.
+impl From<SecuritySchemeKind> for SecurityScheme {
+    fn from(kind: SecuritySchemeKind) -> SecurityScheme {
+        let mut scheme = SecurityScheme {
+            r#type: SecuritySchemeType::MutualTls,
+            description: None,
+            name: None,
+            r#in: None,
+            scheme: None,
+            bearer_format: None,
+            flows: None,
+            open_id_connect_url: None,
+            extensions: HashMap::new(),
+        };
+        match kind {
+            SecuritySchemeKind::ApiKey { name, r#in } => {
+                scheme.r#type = SecuritySchemeType::ApiKey;
+                scheme.name = Some(name);
+                scheme.r#in = Some(r#in);
+            }
+            SecuritySchemeKind::Http {
+                scheme: http_scheme,
+                bearer_format,
+            } => {
+                scheme.r#type = SecuritySchemeType::Http;
+                scheme.scheme = Some(http_scheme);
+                scheme.bearer_format = bearer_format;
+            }
+            SecuritySchemeKind::MutualTls => {}
+            SecuritySchemeKind::Oauth2 { flows } => {
+                scheme.r#type = SecuritySchemeType::Oauth2;
+                scheme.flows = Some(*flows);
+            }
+            SecuritySchemeKind::OpenIdConnect { url } => {
+                scheme.r#type = SecuritySchemeType::OpenIdConnect;
+                scheme.open_id_connect_url = Some(url);
+            }
+        }
+        scheme
+    }
+}
+
+/// Fails if a field required by the type of the security scheme is missing,
+/// see [`SecurityScheme::validate`]. The description, extensions and fields
+/// that don't apply to the type are dropped.
+impl TryFrom<SecurityScheme> for SecuritySchemeKind {
+    type Error = Vec<ValidationError>;
+
+    fn try_from(scheme: SecurityScheme) -> Result<SecuritySchemeKind, Vec<ValidationError>> {
+        let missing = |r#type, field| vec![missing_security_scheme_field("", r#type, field)];
+        let SecurityScheme {
+            r#type,
+            name,
+            r#in,
+            scheme,
+            bearer_format,
+            flows,
+            open_id_connect_url,
+            ..
+        } = scheme;
+        match r#type {
+            SecuritySchemeType::ApiKey => match (name, r#in) {
+                (Some(name), Some(r#in)) => Ok(SecuritySchemeKind::ApiKey { name, r#in }),
+                (name, r#in) => {
+                    let mut errors = Vec::new();
+                    if name.is_none() {
+                        errors.extend(missing("apiKey", "name"));
+                    }
+                    if r#in.is_none() {
+                        errors.extend(missing("apiKey", "in"));
+                    }
+                    Err(errors)
+                }
+            },
+            SecuritySchemeType::Http => scheme
+                .map(|scheme| SecuritySchemeKind::Http {
+                    scheme,
+                    bearer_format,
+                })
+                .ok_or_else(|| missing("http", "scheme")),
+            SecuritySchemeType::MutualTls => Ok(SecuritySchemeKind::MutualTls),
+            SecuritySchemeType::Oauth2 => flows
+                .map(|flows| SecuritySchemeKind::Oauth2 {
+                    flows: Box::new(flows),
+                })
+                .ok_or_else(|| missing("oauth2", "flows")),
+            SecuritySchemeType::OpenIdConnect => open_id_connect_url
+                .map(|url| SecuritySchemeKind::OpenIdConnect { url })
+                .ok_or_else(|| missing("openIdConnect", "openIdConnectUrl")),
+        }
+    }
+}
+
 /// Allows configuration of the supported OAuth Flows.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OauthFlows {
     /// Configuration for the OAuth Implicit flow
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub implicit: Option<OauthFlow>,
     /// Configuration for the OAuth Resource Owner Password flow
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub password: Option<OauthFlow>,
     /// Configuration for the OAuth Client Credentials flow. Previously called
     /// `application` in OpenAPI 2.0.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub client_credentials: Option<OauthFlow>,
     /// Configuration for the OAuth Authorization Code flow. Previously called
     /// `accessCode` in OpenAPI 2.0.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub authorization_code: Option<OauthFlow>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// Configuration details for a supported OAuth Flow.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OauthFlow {
     /// The authorization URL to be used for this flow. This MUST be in the form
@@ -2169,11 +3862,16 @@ pub struct OauthFlow {
     pub token_url: String,
     /// The URL to be used for obtaining refresh tokens. This MUST be in the
     /// form of a URL. The OAuth2 standard requires the use of TLS.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub refresh_url: Option<String>,
     /// The available scopes for the OAuth2 security scheme. A map between the
     /// scope name and a short description for it. The map MAY be empty.
     pub scopes: HashMap<String, String>,
+    /// Specification extensions, the field names begin with `x-`, for example
+    /// `x-internal-id`. The value can be `null`/`None`, a primitive, an array
+    /// or an object.
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: HashMap<String, Any>,
 }
 
 /// Security Requirement Object.
@@ -2207,5 +3905,9 @@ pub type SecurityRequirement = HashMap<String, Vec<String>>;
 
 /// Any value.
 ///
-/// Untyped value.
-pub type Any = serde_json::Value; // TODO: create our own type.
+/// Untyped value, see [`Value`].
+///
+/// Values such as examples and defaults are stored inline in the document,
+/// the methods of this crate return references to them rather than clones,
+/// e.g. [`MediaType::resolved_examples`].
+pub type Any = Value;
@@ -0,0 +1,350 @@
+//! Module with limits for parsing untrusted documents.
+//!
+//! The limits are enforced by wrapping the [`Deserializer`] of the format,
+//! counting all values that are deserialised and tracking how deeply they're
+//! nested. This also counts the values produced by YAML aliases, which are
+//! expanded by the YAML deserialiser.
+
+use std::cell::Cell;
+use std::fmt;
+
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, Visitor};
+
+use crate::ParseError;
+
+/// Limits for a single document.
+pub(crate) struct Limits {
+    max_depth: usize,
+    max_nodes: usize,
+    depth: Cell<usize>,
+    nodes: Cell<usize>,
+    exceeded: Cell<Option<Exceeded>>,
+}
+
+#[derive(Copy, Clone)]
+enum Exceeded {
+    Depth,
+    Nodes,
+}
+
+impl Limits {
+    pub(crate) fn new(max_depth: usize, max_nodes: usize) -> Limits {
+        Limits {
+            max_depth,
+            max_nodes,
+            depth: Cell::new(0),
+            nodes: Cell::new(0),
+            exceeded: Cell::new(None),
+        }
+    }
+
+    /// Returns the error if the limits were exceeded, in which case
+    /// deserialisation failed with a custom error.
+    pub(crate) fn error(&self) -> Option<ParseError> {
+        match self.exceeded.get()? {
+            Exceeded::Depth => Some(ParseError::TooDeep),
+            Exceeded::Nodes => Some(ParseError::TooManyNodes),
+        }
+    }
+
+    /// Called before deserialising a value, must be followed by a call to
+    /// [`Limits::leave`].
+    fn enter<E: de::Error>(&self) -> Result<(), E> {
+        let depth = self.depth.get() + 1;
+        if depth > self.max_depth {
+            self.exceeded.set(Some(Exceeded::Depth));
+            return Err(E::custom(format_args!(
+                "document is nested more than {} levels deep",
+                self.max_depth
+            )));
+        }
+        let nodes = self.nodes.get() + 1;
+        if nodes > self.max_nodes {
+            self.exceeded.set(Some(Exceeded::Nodes));
+            return Err(E::custom(format_args!(
+                "document contains more than {} values",
+                self.max_nodes
+            )));
+        }
+        self.depth.set(depth);
+        self.nodes.set(nodes);
+        Ok(())
+    }
+
+    /// Called after deserialising a value.
+    fn leave(&self) {
+        self.depth.set(self.depth.get() - 1);
+    }
+}
+
+/// Deserialise `T` from `deserializer`, enforcing `limits`.
+pub(crate) fn deserialize<'de, T, D>(deserializer: D, limits: &Limits) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(Limited {
+        inner: deserializer,
+        limits,
+    })
+}
+
+/// Wrapper around [`Deserializer`]s, [`Visitor`]s, etc. enforcing `limits`.
+struct Limited<'l, T> {
+    inner: T,
+    limits: &'l Limits,
+}
+
+impl<'l, T> Limited<'l, T> {
+    fn wrap<U>(&self, inner: U) -> Limited<'l, U> {
+        Limited {
+            inner,
+            limits: self.limits,
+        }
+    }
+}
+
+/// Forward the `deserialize_*` methods, counting the value and tracking the
+/// depth.
+macro_rules! forward_deserialize {
+    ($( $method: ident ( $( $arg: ident : $ty: ty ),* ) ),* $(,)?) => {
+        $(
+        fn $method<V: Visitor<'de>>(self, $( $arg: $ty, )* visitor: V) -> Result<V::Value, D::Error> {
+            self.limits.enter()?;
+            let visitor = self.wrap(visitor);
+            let result = self.inner.$method($( $arg, )* visitor);
+            self.limits.leave();
+            result
+        }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Limited<'_, D> {
+    type Error = D::Error;
+
+    forward_deserialize!(
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    );
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+/// Forward the `visit_*` methods for values without children.
+macro_rules! forward_visit {
+    ($( $method: ident ( $ty: ty ) ),* $(,)?) => {
+        $(
+        fn $method<E: de::Error>(self, v: $ty) -> Result<Self::Value, E> {
+            self.inner.$method(v)
+        }
+        )*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for Limited<'_, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(f)
+    }
+
+    forward_visit!(
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_str(&str),
+        visit_borrowed_str(&'de str),
+        visit_string(String),
+        visit_bytes(&[u8]),
+        visit_borrowed_bytes(&'de [u8]),
+        visit_byte_buf(Vec<u8>),
+    );
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_none()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let deserializer = self.wrap(deserializer);
+        self.inner.visit_some(deserializer)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let deserializer = self.wrap(deserializer);
+        self.inner.visit_newtype_struct(deserializer)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        let seq = self.wrap(seq);
+        self.inner.visit_seq(seq)
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        let map = self.wrap(map);
+        self.inner.visit_map(map)
+    }
+
+    fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        let data = self.wrap(data);
+        self.inner.visit_enum(data)
+    }
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Limited<'_, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Value, D::Error> {
+        let deserializer = self.wrap(deserializer);
+        self.inner.deserialize(deserializer)
+    }
+}
+
+impl<'de, A: de::SeqAccess<'de>> de::SeqAccess<'de> for Limited<'_, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, A::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        let seed = self.wrap(seed);
+        self.inner.next_element_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'de, A: de::MapAccess<'de>> de::MapAccess<'de> for Limited<'_, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let seed = self.wrap(seed);
+        self.inner.next_key_seed(seed)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let seed = self.wrap(seed);
+        self.inner.next_value_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'l, 'de, A: de::EnumAccess<'de>> de::EnumAccess<'de> for Limited<'l, A> {
+    type Error = A::Error;
+    type Variant = Limited<'l, A::Variant>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let limits = self.limits;
+        let seed = Limited {
+            inner: seed,
+            limits,
+        };
+        let (value, variant) = self.inner.variant_seed(seed)?;
+        Ok((
+            value,
+            Limited {
+                inner: variant,
+                limits,
+            },
+        ))
+    }
+}
+
+impl<'de, A: de::VariantAccess<'de>> de::VariantAccess<'de> for Limited<'_, A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, A::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        let seed = self.wrap(seed);
+        self.inner.newtype_variant_seed(seed)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let visitor = self.wrap(visitor);
+        self.inner.tuple_variant(len, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let visitor = self.wrap(visitor);
+        self.inner.struct_variant(fields, visitor)
+    }
+}
@@ -0,0 +1,121 @@
+//! Module with merging of specifications.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::validate::escape;
+#[cfg(doc)]
+use crate::Schema;
+use crate::Spec;
+
+/// Policy for handling conflicting definitions in [`Spec::merge_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergePolicy {
+    /// Return an error, without modifying the specification.
+    #[default]
+    Error,
+    /// The definition of the merged specification wins.
+    LastWins,
+}
+
+/// Error returned by [`Spec::merge`] and [`Schema::flatten_all_of`].
+#[derive(Debug)]
+pub struct MergeConflict {
+    /// Location of the conflicting definition as JSON pointer, e.g.
+    /// `/components/schemas/Pet`.
+    pub location: String,
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "conflicting definitions for `{}`", self.location)
+    }
+}
+
+impl Error for MergeConflict {}
+
+/// Calls `$m!(location, field)` for all maps that are merged.
+macro_rules! merged_maps {
+    ($m: ident) => {
+        $m!("/paths", paths);
+        $m!("/webhooks", webhooks);
+        $m!("/components/schemas", components.schemas);
+        $m!("/components/responses", components.responses);
+        $m!("/components/parameters", components.parameters);
+        $m!("/components/examples", components.examples);
+        $m!("/components/requestBodies", components.request_bodies);
+        $m!("/components/headers", components.headers);
+        $m!("/components/securitySchemes", components.security_schemes);
+        $m!("/components/links", components.links);
+        $m!("/components/callbacks", components.callbacks);
+        $m!("/components/pathItems", components.path_items);
+    };
+}
+
+impl Spec {
+    /// Merge `other` into this specification.
+    ///
+    /// This merges the [`paths`], [`webhooks`], [`components`] and [`tags`],
+    /// all other fields of `other` are ignored. Definitions with the same name
+    /// must be identical, otherwise an error is returned. See
+    /// [`Spec::merge_with`] to configure this.
+    ///
+    /// [`paths`]: Spec::paths
+    /// [`webhooks`]: Spec::webhooks
+    /// [`components`]: Spec::components
+    /// [`tags`]: Spec::tags
+    pub fn merge(&mut self, other: Spec) -> Result<(), MergeConflict> {
+        self.merge_with(other, MergePolicy::Error)
+    }
+
+    /// Same as [`Spec::merge`], but using `policy` to handle conflicting
+    /// definitions.
+    pub fn merge_with(&mut self, other: Spec, policy: MergePolicy) -> Result<(), MergeConflict> {
+        if policy == MergePolicy::Error {
+            self.find_conflict(&other)?;
+        }
+
+        macro_rules! merge {
+            ($location: expr, $($field: ident).+) => {
+                self.$($field).+.extend(other.$($field).+);
+            };
+        }
+        merged_maps!(merge);
+        for tag in other.tags {
+            match self.tags.iter_mut().find(|t| t.name == tag.name) {
+                Some(t) => *t = tag,
+                None => self.tags.push(tag),
+            }
+        }
+        Ok(())
+    }
+
+    fn find_conflict(&self, other: &Spec) -> Result<(), MergeConflict> {
+        macro_rules! check {
+            ($location: expr, $($field: ident).+) => {
+                for (name, value) in &other.$($field).+ {
+                    match self.$($field).+.get(name) {
+                        Some(existing) if existing != value => {
+                            return Err(MergeConflict {
+                                location: format!("{}/{}", $location, escape(name)),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+            };
+        }
+        merged_maps!(check);
+        for tag in &other.tags {
+            match self.tags.iter().position(|t| t.name == tag.name) {
+                Some(i) if self.tags[i] != *tag => {
+                    return Err(MergeConflict {
+                        location: format!("/tags/{}", i),
+                    });
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
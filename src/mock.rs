@@ -0,0 +1,187 @@
+//! Module with generating example values for a [`Schema`].
+
+use indexmap::IndexMap;
+
+use crate::{Format, FormatOrString, Number, Schema, Spec, Type, Value};
+
+/// Maximum depth of (nested) schemas, protects against recursive schemas.
+const MAX_DEPTH: usize = 32;
+
+impl Schema {
+    /// Generate an example value that matches this schema.
+    ///
+    /// If the schema has a `const`, `example`, `examples`, `default` or
+    /// `enum` value (in that order) that value is used. Otherwise a value is
+    /// generated based on the `type`:
+    ///  * `string`: a value based on the `format` (e.g. a UUID for `uuid`) or
+    ///    a string of `minLength` characters.
+    ///  * `integer` and `number`: `0`, or the closest value respecting
+    ///    `minimum` and `maximum`.
+    ///  * `boolean`: `false`.
+    ///  * `array`: `minItems` items.
+    ///  * `object`: all required properties.
+    ///
+    /// `$ref`s are resolved using `spec`, for `allOf` the generated objects
+    /// are merged and for `anyOf` and `oneOf` the first schema is used.
+    /// Recursive schemas are cut off at a certain depth with `null`.
+    pub fn example_value(&self, spec: &Spec) -> Value {
+        example_value(self, spec, MAX_DEPTH)
+    }
+}
+
+fn example_value(schema: &Schema, spec: &Spec, depth: usize) -> Value {
+    if depth == 0 {
+        return Value::Null;
+    }
+    let depth = depth - 1;
+
+    if let Some(reference) = &schema.r#ref {
+        return match spec.resolve_ref::<Schema>(reference) {
+            Some(schema) => example_value(schema, spec, depth),
+            None => Value::Null,
+        };
+    }
+
+    let value = schema
+        .r#const
+        .as_ref()
+        .or(schema.example.as_ref())
+        .or(schema.examples.first())
+        .or(schema.default.as_ref())
+        .or(schema.r#enum.first());
+    if let Some(value) = value {
+        return value.clone();
+    }
+
+    if let Some(all_of) = &schema.all_of {
+        let mut object = IndexMap::new();
+        for schema in all_of {
+            match example_value(schema, spec, depth) {
+                Value::Object(properties) => object.extend(properties),
+                value if all_of.len() == 1 => return value,
+                _ => {}
+            }
+        }
+        if let Value::Object(properties) = object_value(schema, spec, depth) {
+            object.extend(properties);
+        }
+        return Value::Object(object);
+    }
+    let first = [&schema.one_of, &schema.any_of]
+        .into_iter()
+        .find_map(|schemas| schemas.as_ref().and_then(|s| s.first()));
+    if let Some(schema) = first {
+        return example_value(schema, spec, depth);
+    }
+
+    // Prefer a non-null type.
+    let r#type = schema
+        .r#type
+        .iter()
+        .find(|t| **t != Type::Null)
+        .or(schema.r#type.first());
+    let r#type = match r#type {
+        Some(r#type) => r#type,
+        None if schema.properties.is_some() => &Type::Object,
+        None if schema.items.is_some() || !schema.prefix_items.is_empty() => &Type::Array,
+        None => return Value::Null,
+    };
+    match r#type {
+        Type::Null => Value::Null,
+        Type::Boolean => Value::Bool(false),
+        Type::Object => object_value(schema, spec, depth),
+        Type::Array => {
+            let length = schema.min_items.unwrap_or(0).max(schema.prefix_items.len());
+            let items = (0..length)
+                .map(|i| match (schema.prefix_items.get(i), &schema.items) {
+                    (Some(schema), _) => example_value(schema, spec, depth),
+                    (None, Some(schema)) => example_value(schema, spec, depth),
+                    (None, None) => Value::Null,
+                })
+                .collect();
+            Value::Array(items)
+        }
+        Type::Number => {
+            let n = number_value(schema);
+            Value::Number(Number::from_f64(n).unwrap_or_else(|| 0u64.into()))
+        }
+        Type::Integer => {
+            // Positive values are based on the minimum, negative values on
+            // the maximum, so round away from zero to stay in range.
+            let n = number_value(schema);
+            let n = if n > 0.0 { n.ceil() } else { n.floor() };
+            Value::Number((n as i64).into())
+        }
+        Type::String => Value::String(string_value(schema)),
+    }
+}
+
+/// Returns an object with all required properties.
+fn object_value(schema: &Schema, spec: &Spec, depth: usize) -> Value {
+    let properties = schema.properties.as_ref();
+    let object = schema
+        .required
+        .iter()
+        .map(|name| {
+            let value = match properties.and_then(|p| p.get(name)) {
+                Some(schema) => example_value(schema, spec, depth),
+                None => Value::Null,
+            };
+            (name.clone(), value)
+        })
+        .collect();
+    Value::Object(object)
+}
+
+/// Returns zero, or the value closest to zero respecting the minimum and
+/// maximum.
+fn number_value(schema: &Schema) -> f64 {
+    if let Some(minimum) = schema.minimum {
+        if minimum > 0.0 {
+            return minimum;
+        }
+    }
+    if let Some(minimum) = schema.exclusive_minimum {
+        if minimum >= 0.0 {
+            return minimum + 1.0;
+        }
+    }
+    if let Some(maximum) = schema.maximum {
+        if maximum < 0.0 {
+            return maximum;
+        }
+    }
+    if let Some(maximum) = schema.exclusive_maximum {
+        if maximum <= 0.0 {
+            return maximum - 1.0;
+        }
+    }
+    0.0
+}
+
+fn string_value(schema: &Schema) -> String {
+    let value = match &schema.format {
+        Some(FormatOrString::Format(format)) => match format {
+            Format::DateTime => "1970-01-01T00:00:00Z",
+            Format::Date => "1970-01-01",
+            Format::Time => "00:00:00Z",
+            Format::Duration => "P1D",
+            Format::Email | Format::IdnEmail => "user@example.com",
+            Format::Hostname | Format::IdnHostname => "example.com",
+            Format::Ipv4 | Format::Ip => "127.0.0.1",
+            Format::Ipv6 => "::1",
+            Format::Uri | Format::Iri => "https://example.com",
+            Format::UriReference | Format::IriReference => "/",
+            Format::Uuid => "00000000-0000-0000-0000-000000000000",
+            Format::JsonPointer => "/",
+            Format::RelativeJsonPointer => "0",
+            _ => "",
+        },
+        _ => "",
+    };
+    let mut value = value.to_owned();
+    for _ in value.chars().count()..schema.min_length.unwrap_or(0) {
+        value.push('a');
+    }
+    value
+}
@@ -1,30 +1,77 @@
 //! Module with spec parsing.
 
-use std::fs::File;
-use std::io::{self, BufReader};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
 use std::path::Path;
 
-use crate::Spec;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::limit::{self, Limits};
+use crate::validate::escape;
+use crate::{Spec, Value};
 
 /// Read a JSON or YAML [Open API Specification].
 ///
+/// The format is determined by the extension of the file: `.json`, `.yaml`
+/// or `.yml`, ignoring case. If both the `json` and `yaml` features are
+/// enabled and the extension is missing or unknown the format is detected
+/// based on the content, see [`read_from_reader`].
+///
 /// [Open API Specification]: Spec
-#[cfg(any(feature = "json", feature = "yaml"))]
 pub fn read_from_file<P: AsRef<Path>>(path: P) -> io::Result<Spec> {
-    _read_from_file(path.as_ref())
+    _read_from_file(path.as_ref(), &ParseOptions::new())
 }
 
-#[cfg(any(feature = "json", feature = "yaml"))]
-fn _read_from_file(path: &Path) -> io::Result<Spec> {
-    match path.extension().and_then(|e| e.to_str()) {
-        #[cfg(feature = "json")]
-        Some("json") => _read_from_json_file(path),
-        #[cfg(feature = "yaml")]
-        Some("yaml") => _read_from_yaml_file(path),
-        _ => Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            "unsupported file format",
-        )),
+fn _read_from_file(path: &Path, options: &ParseOptions) -> io::Result<Spec> {
+    match Format::from_path(path) {
+        Ok(format) => from_file(path, |input| options.parse(format, input)),
+        #[cfg(all(feature = "json", feature = "yaml"))]
+        Err(_) => from_file(path, |input| options.parse(Format::detect(input), input)),
+        #[cfg(not(all(feature = "json", feature = "yaml")))]
+        Err(err) => Err(err),
+    }
+}
+
+/// Format of a document.
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum Format {
+    #[cfg(feature = "json")]
+    Json,
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl Format {
+    /// Determine the format based on the extension of `path`, ignoring case.
+    pub(crate) fn from_path(path: &Path) -> io::Result<Format> {
+        let extension = path.extension().and_then(|e| e.to_str());
+        match extension.map(str::to_ascii_lowercase).as_deref() {
+            #[cfg(feature = "json")]
+            Some("json") => Ok(Format::Json),
+            #[cfg(feature = "yaml")]
+            Some("yaml" | "yml") => Ok(Format::Yaml),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "unsupported file format",
+            )),
+        }
+    }
+
+    /// Determine the format based on the first non-whitespace character of
+    /// `input`, see [`read_from_reader`].
+    #[cfg(any(feature = "reqwest", all(feature = "json", feature = "yaml")))]
+    pub(crate) fn detect(input: &str) -> Format {
+        match input.trim_start().as_bytes().first() {
+            #[cfg(feature = "json")]
+            Some(b'{' | b'[') => Format::Json,
+            #[cfg(feature = "yaml")]
+            _ => Format::Yaml,
+            #[cfg(not(feature = "yaml"))]
+            _ => Format::Json,
+        }
     }
 }
 
@@ -36,9 +83,7 @@ pub fn read_from_json_file<P: AsRef<Path>>(path: P) -> io::Result<Spec> {
 
 #[cfg(feature = "json")]
 fn _read_from_json_file(path: &Path) -> io::Result<Spec> {
-    from_file(path, |file| {
-        serde_json::from_reader(file).map_err(Into::into)
-    })
+    from_file(path, Spec::from_json_str)
 }
 
 /// [`read_from_file`], but only for YAML files.
@@ -49,15 +94,395 @@ pub fn read_from_yaml_file<P: AsRef<Path>>(path: P) -> io::Result<Spec> {
 
 #[cfg(feature = "yaml")]
 fn _read_from_yaml_file(path: &Path) -> io::Result<Spec> {
-    from_file(path, |file| {
-        serde_yaml::from_reader(file).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
-    })
+    from_file(path, Spec::from_yaml_str)
 }
 
 fn from_file<P>(path: &Path, parse: P) -> io::Result<Spec>
 where
-    P: FnOnce(BufReader<File>) -> io::Result<Spec>,
+    P: FnOnce(&str) -> Result<Spec, ParseError>,
 {
-    let file = BufReader::new(File::open(path)?);
-    parse(file)
+    let data = fs::read_to_string(path)?;
+    parse(&data).map_err(Into::into)
+}
+
+/// Read a JSON or YAML [Open API Specification] from `reader`.
+///
+/// The format is determined by the first non-whitespace byte: `{` or `[`
+/// means JSON, anything else is parsed as YAML.
+///
+/// The entire input is read into memory before it's parsed, which allows the
+/// limits of the default [`ParseOptions`] to be applied.
+///
+/// [Open API Specification]: Spec
+pub fn read_from_reader<R: Read>(reader: R) -> io::Result<Spec> {
+    let mut reader = BufReader::new(reader);
+    match first_non_whitespace(&mut reader)? {
+        #[cfg(feature = "json")]
+        Some(b'{' | b'[') => _read_from_json_reader(reader),
+        #[cfg(feature = "yaml")]
+        _ => _read_from_yaml_reader(reader),
+        #[cfg(not(feature = "yaml"))]
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "unsupported file format",
+        )),
+    }
+}
+
+/// Returns the first non-whitespace byte in `reader`, without consuming it.
+fn first_non_whitespace<R: BufRead>(reader: &mut R) -> io::Result<Option<u8>> {
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            return Ok(None);
+        }
+        match buf.iter().position(|b| !b.is_ascii_whitespace()) {
+            Some(idx) => {
+                let b = buf[idx];
+                reader.consume(idx);
+                return Ok(Some(b));
+            }
+            None => {
+                let n = buf.len();
+                reader.consume(n);
+            }
+        }
+    }
+}
+
+/// [`read_from_reader`], but only for JSON.
+///
+/// Like [`read_from_reader`] this reads the entire input into memory.
+#[cfg(feature = "json")]
+pub fn read_from_json_reader<R: Read>(reader: R) -> io::Result<Spec> {
+    _read_from_json_reader(BufReader::new(reader))
+}
+
+#[cfg(feature = "json")]
+fn _read_from_json_reader<R: Read>(mut reader: BufReader<R>) -> io::Result<Spec> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Spec::from_json_str(&input).map_err(Into::into)
+}
+
+/// [`read_from_reader`], but only for YAML.
+///
+/// Like [`read_from_reader`] this reads the entire input into memory.
+#[cfg(feature = "yaml")]
+pub fn read_from_yaml_reader<R: Read>(reader: R) -> io::Result<Spec> {
+    _read_from_yaml_reader(BufReader::new(reader))
+}
+
+#[cfg(feature = "yaml")]
+fn _read_from_yaml_reader<R: Read>(mut reader: BufReader<R>) -> io::Result<Spec> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Spec::from_yaml_str(&input).map_err(Into::into)
+}
+
+impl Spec {
+    /// Parse a JSON [Open API Specification] from `input`.
+    ///
+    /// [Open API Specification]: Spec
+    #[cfg(feature = "json")]
+    pub fn from_json_str(input: &str) -> Result<Spec, ParseError> {
+        ParseOptions::new().from_json_str(input)
+    }
+
+    /// Parse a YAML [Open API Specification] from `input`.
+    ///
+    /// This uses the default [`ParseOptions`], which protect against
+    /// excessive expansion of YAML aliases, see [`ParseOptions::max_nodes`].
+    ///
+    /// [Open API Specification]: Spec
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_str(input: &str) -> Result<Spec, ParseError> {
+        ParseOptions::new().from_yaml_str(input)
+    }
+}
+
+/// Options for parsing a [`Spec`].
+///
+/// The default options match the parsing done by functions such as
+/// [`read_from_file`] and [`Spec::from_json_str`].
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    deny_unknown_fields: bool,
+    max_depth: usize,
+    max_nodes: Option<usize>,
+}
+
+impl ParseOptions {
+    /// Create the default parsing options.
+    pub const fn new() -> ParseOptions {
+        ParseOptions {
+            deny_unknown_fields: false,
+            max_depth: 127,
+            max_nodes: None,
+        }
+    }
+
+    /// Return [`ParseError::UnknownFields`] if the document contains fields
+    /// that are not part of the specification and are not an extension
+    /// (i.e. don't start with `x-`). Defaults to `false`, ignoring unknown
+    /// fields.
+    ///
+    /// Note that [`Schema`]s accept any field (as JSON Schema does), so
+    /// unknown fields in schemas are not reported. Fields with an empty value
+    /// (e.g. `null` or `[]`) are not reported either.
+    ///
+    /// [`Schema`]: crate::Schema
+    pub const fn deny_unknown_fields(mut self, deny: bool) -> ParseOptions {
+        self.deny_unknown_fields = deny;
+        self
+    }
+
+    /// Return [`ParseError::TooDeep`] if values in the document are nested
+    /// more than `max` levels deep, where the document itself is the first
+    /// level. Defaults to 127.
+    ///
+    /// Every object, array and scalar counts as a level. For example a
+    /// schema nested using `items` adds one level, while a schema nested
+    /// using `properties` adds two: the `properties` object and the schema.
+    ///
+    /// This protects against overflowing the stack when parsing deeply
+    /// nested, recursive structures such as [`Schema`]s. Note that the JSON
+    /// and YAML parsers also limit the nesting to 127 arrays and objects,
+    /// returning a [`ParseError::Json`] or [`ParseError::Yaml`] error, so
+    /// increasing the limit beyond the default has no effect. For YAML the
+    /// parser's limit is checked first.
+    ///
+    /// The default is sized for the stack of the main thread, when parsing on
+    /// threads with a small stack a lower limit may be needed.
+    ///
+    /// [`Schema`]: crate::Schema
+    pub const fn max_depth(mut self, max: usize) -> ParseOptions {
+        self.max_depth = max;
+        self
+    }
+
+    /// Return [`ParseError::TooManyNodes`] if the document contains more than
+    /// `max` values (objects, arrays, strings, etc.).
+    ///
+    /// This protects against YAML documents that use aliases (`*name`) to
+    /// expand into a huge document, e.g. the "billion laughs" attack, which
+    /// could otherwise exhaust the memory when parsing untrusted documents.
+    /// Defaults to the length of the input in bytes (with a minimum of
+    /// 1024), which is always enough for documents without aliases.
+    pub const fn max_nodes(mut self, max: usize) -> ParseOptions {
+        self.max_nodes = Some(max);
+        self
+    }
+
+    /// Read a JSON or YAML [Open API Specification] using these options.
+    ///
+    /// See [`read_from_file`].
+    ///
+    /// [Open API Specification]: Spec
+    pub fn read_from_file<P: AsRef<Path>>(&self, path: P) -> io::Result<Spec> {
+        _read_from_file(path.as_ref(), self)
+    }
+
+    /// Parse `input` in `format` using these options.
+    pub(crate) fn parse(&self, format: Format, input: &str) -> Result<Spec, ParseError> {
+        match format {
+            #[cfg(feature = "json")]
+            Format::Json => self.from_json_str(input),
+            #[cfg(feature = "yaml")]
+            Format::Yaml => self.from_yaml_str(input),
+        }
+    }
+
+    /// Deserialise `T` from `input` in `format`, enforcing the limits (but not
+    /// [`ParseOptions::deny_unknown_fields`]).
+    pub(crate) fn deserialize<T>(&self, format: Format, input: &str) -> Result<T, ParseError>
+    where
+        T: DeserializeOwned,
+    {
+        let max_nodes = self.max_nodes.unwrap_or(input.len().max(1024));
+        let limits = Limits::new(self.max_depth, max_nodes);
+        match format {
+            #[cfg(feature = "json")]
+            Format::Json => {
+                let mut deserializer = serde_json::Deserializer::from_str(input);
+                let value = match limit::deserialize(&mut deserializer, &limits) {
+                    Ok(value) => value,
+                    Err(err) => return Err(limits.error().unwrap_or(ParseError::Json(err))),
+                };
+                deserializer.end().map_err(ParseError::Json)?;
+                Ok(value)
+            }
+            #[cfg(feature = "yaml")]
+            Format::Yaml => {
+                let deserializer = serde_yaml::Deserializer::from_str(input);
+                limit::deserialize(deserializer, &limits)
+                    .map_err(|err| limits.error().unwrap_or(ParseError::Yaml(err)))
+            }
+        }
+    }
+
+    /// Parse a JSON [Open API Specification] from `input` using these
+    /// options.
+    ///
+    /// [Open API Specification]: Spec
+    #[cfg(feature = "json")]
+    pub fn from_json_str(&self, input: &str) -> Result<Spec, ParseError> {
+        let spec = self.deserialize(Format::Json, input)?;
+        if self.deny_unknown_fields {
+            let input = serde_json::from_str(input).map_err(ParseError::Json)?;
+            check_unknown_fields(&input, &spec)?;
+        }
+        Ok(spec)
+    }
+
+    /// Parse a YAML [Open API Specification] from `input` using these
+    /// options.
+    ///
+    /// [Open API Specification]: Spec
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_str(&self, input: &str) -> Result<Spec, ParseError> {
+        let spec = self.deserialize(Format::Yaml, input)?;
+        if self.deny_unknown_fields {
+            let input = serde_yaml::from_str(input).map_err(ParseError::Yaml)?;
+            check_unknown_fields(&input, &spec)?;
+        }
+        Ok(spec)
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        ParseOptions::new()
+    }
+}
+
+/// Checks for unknown fields in `input` by comparing it to the fields of the
+/// parsed `spec`.
+fn check_unknown_fields(input: &Value, spec: &Spec) -> Result<(), ParseError> {
+    #[cfg(feature = "json")]
+    let output = serde_json::to_value(spec)
+        .and_then(Value::deserialize)
+        .map_err(ParseError::Json)?;
+    #[cfg(not(feature = "json"))]
+    let output = serde_yaml::to_value(spec)
+        .and_then(Value::deserialize)
+        .map_err(ParseError::Yaml)?;
+    let mut unknown = Vec::new();
+    unknown_fields(input, &output, &mut String::new(), &mut unknown);
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(ParseError::UnknownFields(unknown))
+    }
+}
+
+/// Adds the locations of all fields in `input` that are not in `output` to
+/// `unknown`.
+fn unknown_fields(input: &Value, output: &Value, location: &mut String, unknown: &mut Vec<String>) {
+    match (input, output) {
+        (Value::Object(input), Value::Object(output)) => {
+            for (name, value) in input {
+                let length = location.len();
+                location.push('/');
+                location.push_str(&escape(name));
+                match output.get(name) {
+                    Some(output) => unknown_fields(value, output, location, unknown),
+                    None if name.starts_with("x-") || is_empty(value) => {}
+                    // OpenAPI v3.0 fields that are normalised, see `Schema`.
+                    None if name == "nullable" => {}
+                    None if matches!(&**name, "exclusiveMaximum" | "exclusiveMinimum")
+                        && matches!(value, Value::Bool(_)) => {}
+                    // JSON Schema draft 7 alias, serialised as `$defs`.
+                    None if name == "definitions" => {
+                        if let Some(output) = output.get("$defs") {
+                            unknown_fields(value, output, location, unknown);
+                        }
+                    }
+                    None => unknown.push(location.clone()),
+                }
+                location.truncate(length);
+            }
+        }
+        (Value::Array(input), Value::Array(output)) => {
+            for (i, (input, output)) in input.iter().zip(output).enumerate() {
+                let length = location.len();
+                location.push('/');
+                location.push_str(&i.to_string());
+                unknown_fields(input, output, location, unknown);
+                location.truncate(length);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::Array(values) => values.is_empty(),
+        Value::Object(object) => object.is_empty(),
+        _ => false,
+    }
+}
+
+/// Error returned when parsing a [`Spec`] fails.
+#[derive(Debug)]
+pub enum ParseError {
+    /// Error parsing JSON.
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    /// Error parsing YAML.
+    #[cfg(feature = "yaml")]
+    Yaml(serde_yaml::Error),
+    /// The document contains unknown fields, see
+    /// [`ParseOptions::deny_unknown_fields`]. Contains the locations of the
+    /// fields as JSON pointer, e.g. `/paths/~1pets/get/descriptions`.
+    UnknownFields(Vec<String>),
+    /// The document contains too many values, see
+    /// [`ParseOptions::max_nodes`].
+    TooManyNodes,
+    /// The document is nested too deeply, see [`ParseOptions::max_depth`].
+    TooDeep,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(feature = "json")]
+            ParseError::Json(err) => write!(f, "invalid JSON: {}", err),
+            #[cfg(feature = "yaml")]
+            ParseError::Yaml(err) => write!(f, "invalid YAML: {}", err),
+            ParseError::UnknownFields(locations) => {
+                write!(f, "unknown fields: {}", locations.join(", "))
+            }
+            ParseError::TooManyNodes => f.write_str("document contains too many values"),
+            ParseError::TooDeep => f.write_str("document is nested too deeply"),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            #[cfg(feature = "json")]
+            ParseError::Json(err) => Some(err),
+            #[cfg(feature = "yaml")]
+            ParseError::Yaml(err) => Some(err),
+            ParseError::UnknownFields(_) | ParseError::TooManyNodes | ParseError::TooDeep => None,
+        }
+    }
+}
+
+impl From<ParseError> for io::Error {
+    fn from(err: ParseError) -> io::Error {
+        match err {
+            #[cfg(feature = "json")]
+            ParseError::Json(err) => err.into(),
+            #[cfg(feature = "yaml")]
+            ParseError::Yaml(err) => io::Error::new(io::ErrorKind::InvalidData, err),
+            err @ (ParseError::UnknownFields(_)
+            | ParseError::TooManyNodes
+            | ParseError::TooDeep) => io::Error::new(io::ErrorKind::InvalidData, err),
+        }
+    }
 }
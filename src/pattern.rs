@@ -0,0 +1,26 @@
+//! Module with compiling of regular expressions.
+
+use regex::Regex;
+
+use crate::Schema;
+
+impl Schema {
+    /// Returns the compiled [`pattern`], if any.
+    ///
+    /// The specification uses the [ECMA-262] regular expression dialect, while
+    /// this uses the [`regex`] crate, which differs in a number of ways:
+    ///  * lookarounds (e.g. `(?=...)`) and backreferences (e.g. `\1`) are not
+    ///    supported, patterns using them fail to compile,
+    ///  * `\d`, `\w` and `\s` match Unicode characters, rather than only ASCII
+    ///    characters,
+    ///  * `\u{...}` and `\p{...}` escapes are always allowed, ECMA-262 requires
+    ///    the `u` flag.
+    ///
+    /// Note that, like in ECMA-262, the pattern is not implicitly anchored.
+    ///
+    /// [`pattern`]: Schema::pattern
+    /// [ECMA-262]: https://262.ecma-international.org/#sec-regexp-regular-expression-objects
+    pub fn compiled_pattern(&self) -> Option<Result<Regex, regex::Error>> {
+        self.pattern.as_deref().map(Regex::new)
+    }
+}
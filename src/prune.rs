@@ -0,0 +1,131 @@
+//! Module with removing unused components.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::validate::unescape;
+use crate::visit::is_schema_name;
+use crate::{HttpMethod, Operation, Schema, Spec, Visitor};
+
+/// Component as `(field, name)`, e.g. `("schemas", "Pet")`.
+type Key = (String, String);
+
+impl Spec {
+    /// Remove all components that are not used, returning the number of
+    /// removed components.
+    ///
+    /// A component is used if it's referenced from [`Spec::paths`],
+    /// [`Spec::webhooks`] or [`Spec::security`], either directly or via other
+    /// used components (e.g. a schema referenced by a used schema). Security
+    /// schemes are used if they're named in a security requirement.
+    ///
+    /// This is useful after removing operations, e.g. using
+    /// [`Spec::retain_operations`]. Note that only local references, such as
+    /// `#/components/schemas/Pet`, are followed.
+    pub fn prune_unused_components(&mut self) -> usize {
+        let mut collector = Collector {
+            roots: Vec::new(),
+            edges: HashMap::new(),
+        };
+        self.walk(&mut collector);
+        for requirement in &self.security {
+            for name in requirement.keys() {
+                collector.roots.push(security_scheme(name));
+            }
+        }
+
+        let mut used = HashSet::new();
+        let mut queue = collector.roots;
+        while let Some(key) = queue.pop() {
+            if used.contains(&key) {
+                continue;
+            }
+            if let Some(targets) = collector.edges.get(&key) {
+                queue.extend(targets.iter().cloned());
+            }
+            used.insert(key);
+        }
+
+        let mut removed = 0;
+        let components = &mut self.components;
+        macro_rules! prune {
+            ($( $field: ident => $name: expr ),* $(,)?) => {
+                $(
+                let length = components.$field.len();
+                components
+                    .$field
+                    .retain(|name, _| used.contains(&($name.to_owned(), name.clone())));
+                removed += length - components.$field.len();
+                )*
+            };
+        }
+        prune!(
+            schemas => "schemas",
+            responses => "responses",
+            parameters => "parameters",
+            examples => "examples",
+            request_bodies => "requestBodies",
+            headers => "headers",
+            security_schemes => "securitySchemes",
+            links => "links",
+            callbacks => "callbacks",
+            path_items => "pathItems",
+        );
+        removed
+    }
+}
+
+/// Collects the references to components.
+struct Collector {
+    /// Components used outside of the components.
+    roots: Vec<Key>,
+    /// Component -> components it uses.
+    edges: HashMap<Key, Vec<Key>>,
+}
+
+impl Collector {
+    fn add(&mut self, location: &str, target: Key) {
+        match component(location, "/components/") {
+            Some(owner) => self.edges.entry(owner).or_default().push(target),
+            None => self.roots.push(target),
+        }
+    }
+}
+
+impl Visitor<'_> for Collector {
+    fn visit_operation(&mut self, location: &str, _: HttpMethod, operation: &Operation) {
+        for requirement in &operation.security {
+            for name in requirement.keys() {
+                self.add(location, security_scheme(name));
+            }
+        }
+    }
+
+    fn visit_schema(&mut self, location: &str, schema: &Schema) {
+        // References in the mapping are passed to `visit_reference`, but schema
+        // names are not.
+        if let Some(discriminator) = &schema.discriminator {
+            for name in discriminator.mapping.values() {
+                if is_schema_name(name) {
+                    self.add(location, ("schemas".to_owned(), name.clone()));
+                }
+            }
+        }
+    }
+
+    fn visit_reference(&mut self, location: &str, reference: &str) {
+        if let Some(target) = component(reference, "#/components/") {
+            self.add(location, target);
+        }
+    }
+}
+
+/// Returns the component `pointer` points into, if it starts with `prefix`.
+fn component(pointer: &str, prefix: &str) -> Option<Key> {
+    let (field, name) = pointer.strip_prefix(prefix)?.split_once('/')?;
+    let name = name.split('/').next().unwrap_or(name);
+    Some((field.to_owned(), unescape(name).into_owned()))
+}
+
+fn security_scheme(name: &str) -> Key {
+    ("securitySchemes".to_owned(), name.to_owned())
+}
@@ -0,0 +1,599 @@
+//! Module with reference resolving.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::validate::{escape, parse_pointer};
+use crate::visit::is_schema_name;
+use crate::{
+    Any, BoolOr, Callback, Components, Example, Header, Link, MediaType, Operation, Parameter,
+    PathItem, Reference, RequestBody, Response, Schema, SecurityScheme, Spec,
+};
+
+/// Maximum number of references followed in a single lookup, protects against
+/// reference cycles.
+const MAX_DEPTH: usize = 32;
+
+/// Type that can be stored in [`Components`] and thus can be referenced using
+/// a local reference, e.g. `#/components/schemas/Pet`.
+pub trait Component: Sized {
+    /// Name of the field in [`Components`] as used in references, e.g.
+    /// `schemas` for [`Schema`].
+    const FIELD: &'static str;
+
+    /// Returns the component called `name`, following at most `depth`
+    /// references.
+    #[doc(hidden)]
+    fn lookup<'a>(spec: &'a Spec, name: &str, depth: usize) -> Option<&'a Self>;
+
+    /// Returns `true` if the component called `name` exists, without
+    /// following references.
+    #[doc(hidden)]
+    fn contains(spec: &Spec, name: &str) -> bool;
+
+    /// Add `component` to `components` as `name`.
+    #[doc(hidden)]
+    fn insert(components: &mut Components, name: String, component: Self);
+}
+
+/// Implement [`Component`] for a type stored as `Reference<T>`.
+macro_rules! reference_component {
+    ($( $ty: ty => $field: ident, $name: expr ),* $(,)?) => {
+        $(
+        impl Component for $ty {
+            const FIELD: &'static str = $name;
+
+            fn lookup<'a>(spec: &'a Spec, name: &str, depth: usize) -> Option<&'a Self> {
+                spec.components.$field.get(name)?.resolve_depth(spec, depth)
+            }
+
+            fn contains(spec: &Spec, name: &str) -> bool {
+                spec.components.$field.contains_key(name)
+            }
+
+            fn insert(components: &mut Components, name: String, component: Self) {
+                let reference = Reference {
+                    r#ref: None,
+                    summary: None,
+                    description: None,
+                    object: Some(component),
+                };
+                components.$field.insert(name, reference);
+            }
+        }
+        )*
+    };
+}
+
+reference_component!(
+    Response => responses, "responses",
+    Parameter => parameters, "parameters",
+    Example => examples, "examples",
+    RequestBody => request_bodies, "requestBodies",
+    Header => headers, "headers",
+    SecurityScheme => security_schemes, "securitySchemes",
+    Link => links, "links",
+    Callback => callbacks, "callbacks",
+);
+
+impl Component for Schema {
+    const FIELD: &'static str = "schemas";
+
+    fn lookup<'a>(spec: &'a Spec, name: &str, _: usize) -> Option<&'a Self> {
+        spec.components.schemas.get(name)
+    }
+
+    fn contains(spec: &Spec, name: &str) -> bool {
+        spec.components.schemas.contains_key(name)
+    }
+
+    fn insert(components: &mut Components, name: String, component: Self) {
+        components.schemas.insert(name, component);
+    }
+}
+
+impl Component for PathItem {
+    const FIELD: &'static str = "pathItems";
+
+    fn lookup<'a>(spec: &'a Spec, name: &str, _: usize) -> Option<&'a Self> {
+        spec.components.path_items.get(name)
+    }
+
+    fn contains(spec: &Spec, name: &str) -> bool {
+        spec.components.path_items.contains_key(name)
+    }
+
+    fn insert(components: &mut Components, name: String, component: Self) {
+        components.path_items.insert(name, component);
+    }
+}
+
+impl Spec {
+    /// Resolve a local `reference`, e.g. `#/components/schemas/Pet`.
+    ///
+    /// Only references into the [`Components`] object are supported, returns
+    /// `None` for external references (e.g. `pet.yaml`) or if the component
+    /// doesn't exist.
+    ///
+    /// [`Components`]: crate::Components
+    pub fn resolve_ref<T: Component>(&self, reference: &str) -> Option<&T> {
+        resolve_ref(self, reference, MAX_DEPTH)
+    }
+
+    /// Returns the schema called `name` in [`Components::schemas`], following
+    /// its `$ref` if it's a reference to another component schema.
+    ///
+    /// Only a single level of references is followed. Returns `None` if the
+    /// schema or the referenced schema doesn't exist.
+    ///
+    /// [`Components::schemas`]: crate::Components::schemas
+    pub fn resolved_schema(&self, name: &str) -> Option<&Schema> {
+        let schema = self.schema(name)?;
+        match &schema.r#ref {
+            Some(reference) => self.resolve_ref(reference),
+            None => Some(schema),
+        }
+    }
+}
+
+fn resolve_ref<'a, T: Component>(spec: &'a Spec, reference: &str, depth: usize) -> Option<&'a T> {
+    T::lookup(spec, &component_name::<T>(reference)?, depth)
+}
+
+/// Returns the name of the component `reference` refers to, if it's a local
+/// reference to a component of type `T`.
+fn component_name<T: Component>(reference: &str) -> Option<Cow<'_, str>> {
+    let tokens = parse_pointer(reference.strip_prefix('#')?)?;
+    match <[_; 3]>::try_from(tokens) {
+        Ok([components, field, name]) if components == "components" && field == T::FIELD => {
+            Some(name)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the name of the component `reference` points into, e.g. `Pet` for
+/// `#/components/schemas/Pet/properties/id`.
+fn component_prefix<T: Component>(reference: &str) -> Option<Cow<'_, str>> {
+    let mut tokens = parse_pointer(reference.strip_prefix('#')?)?.into_iter();
+    match (tokens.next(), tokens.next(), tokens.next()) {
+        (Some(components), Some(field), Some(name))
+            if components == "components" && field == T::FIELD =>
+        {
+            Some(name)
+        }
+        _ => None,
+    }
+}
+
+impl<T: Component> Reference<T> {
+    /// Resolve the reference.
+    ///
+    /// If this is a reference (i.e. `$ref` is set) it's resolved using
+    /// [`Spec::resolve_ref`], otherwise this returns the inline object.
+    pub fn resolve<'a>(&'a self, spec: &'a Spec) -> Option<&'a T> {
+        self.resolve_depth(spec, MAX_DEPTH)
+    }
+
+    fn resolve_depth<'a>(&'a self, spec: &'a Spec, depth: usize) -> Option<&'a T> {
+        match &self.r#ref {
+            Some(reference) if depth != 0 => resolve_ref(spec, reference, depth - 1),
+            Some(_) => None,
+            None => self.object.as_ref(),
+        }
+    }
+}
+
+impl PathItem {
+    /// Resolve the path item.
+    ///
+    /// If [`PathItem::ref`] is set it's resolved using [`Spec::resolve_ref`],
+    /// otherwise this returns the path item itself. The behaviour is undefined
+    /// if the path item defines fields besides `$ref`, so the inline fields are
+    /// preferred: the path item itself is returned if it defines any
+    /// operation. It's also returned if the reference can't be resolved.
+    pub fn resolve<'a>(&'a self, spec: &'a Spec) -> &'a PathItem {
+        match self.resolved_name(spec) {
+            Some(name) => &spec.components.path_items[&*name],
+            None => self,
+        }
+    }
+
+    /// Returns the name of the path item in [`Components::path_items`] this
+    /// path item resolves to, or `None` if [`PathItem::resolve`] returns the
+    /// path item itself.
+    pub(crate) fn resolved_name<'a>(&'a self, spec: &'a Spec) -> Option<Cow<'a, str>> {
+        let mut path_item = self;
+        let mut resolved = None;
+        for _ in 0..MAX_DEPTH {
+            let reference = match &path_item.r#ref {
+                Some(reference) if path_item.operations().next().is_none() => reference,
+                _ => break,
+            };
+            let name = match component_name::<PathItem>(reference) {
+                Some(name) => name,
+                None => break,
+            };
+            match spec.components.path_items.get(&*name) {
+                Some(referenced) => path_item = referenced,
+                None => break,
+            }
+            resolved = Some(name);
+        }
+        resolved
+    }
+}
+
+/// Implement the example resolving methods for types with an `examples` field.
+macro_rules! resolve_examples {
+    ($( $ty: ident ),* $(,)?) => {
+        $(
+        impl $ty {
+            #[doc = concat!(
+                "Returns the inline values of [`", stringify!($ty), "::examples`], ",
+                "following references.",
+            )]
+            ///
+            /// Examples that can't be resolved or that only have an
+            /// [`Example::external_value`] are not included, see
+            #[doc = concat!("[`", stringify!($ty), "::external_examples`].")]
+            pub fn resolved_examples<'a>(&'a self, spec: &'a Spec) -> HashMap<&'a str, &'a Any> {
+                resolve_examples(&self.examples, spec, |example| example.value.as_ref())
+            }
+
+            #[doc = concat!(
+                "Returns the [`Example::external_value`]s of [`", stringify!($ty),
+                "::examples`], following references.",
+            )]
+            pub fn external_examples<'a>(&'a self, spec: &'a Spec) -> HashMap<&'a str, &'a str> {
+                resolve_examples(&self.examples, spec, |example| {
+                    example.external_value.as_deref()
+                })
+            }
+        }
+        )*
+    };
+}
+
+resolve_examples!(MediaType, Parameter, Header);
+
+fn resolve_examples<'a, T: ?Sized, F>(
+    examples: &'a HashMap<String, Reference<Example>>,
+    spec: &'a Spec,
+    value: F,
+) -> HashMap<&'a str, &'a T>
+where
+    F: Fn(&'a Example) -> Option<&'a T>,
+{
+    examples
+        .iter()
+        .filter_map(|(name, example)| Some((&**name, value(example.resolve(spec)?)?)))
+        .collect()
+}
+
+/// Reference that can't be resolved, returned by [`Spec::check_references`].
+#[derive(Debug)]
+pub struct BrokenReference {
+    /// Location of the reference in the document as JSON pointer, e.g.
+    /// `/paths/~1pets/get/responses/200`.
+    pub location: String,
+    /// The reference (`$ref`) itself, e.g. `#/components/schemas/Pet`.
+    pub reference: String,
+    /// Why the reference is broken.
+    pub kind: BrokenReferenceKind,
+}
+
+/// Why a [`BrokenReference`] couldn't be resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrokenReferenceKind {
+    /// Local reference to a component that doesn't exist.
+    Missing,
+    /// Reference to an external document, e.g. `pet.yaml`, which are not
+    /// resolved.
+    External,
+}
+
+impl Spec {
+    /// Check all references in the document.
+    ///
+    /// This checks all [`Reference`]s, [`PathItem::ref`]s, [`Schema::ref`]s and
+    /// [`Discriminator::mapping`] values and returns the references that can't
+    /// be resolved. Note that for schemas only references into the
+    /// [`Components`] object are checked. For references pointing inside a
+    /// component, e.g. `#/components/schemas/Pet/properties/id`, only the
+    /// existence of the component is checked.
+    ///
+    /// [`Discriminator::mapping`]: crate::Discriminator::mapping
+    pub fn check_references(&self) -> Vec<BrokenReference> {
+        let mut checker = RefChecker {
+            spec: self,
+            broken: Vec::new(),
+        };
+        checker.spec();
+        checker.broken
+    }
+}
+
+struct RefChecker<'s> {
+    spec: &'s Spec,
+    broken: Vec<BrokenReference>,
+}
+
+impl<'s> RefChecker<'s> {
+    fn check<T: Component>(&mut self, location: &str, reference: &str) {
+        let kind = if !reference.starts_with('#') {
+            BrokenReferenceKind::External
+        } else if component_prefix::<T>(reference).is_some_and(|name| T::contains(self.spec, &name))
+        {
+            return;
+        } else {
+            BrokenReferenceKind::Missing
+        };
+        self.broken.push(BrokenReference {
+            location: location.to_owned(),
+            reference: reference.to_owned(),
+            kind,
+        });
+    }
+
+    /// Check `reference`, or if it's an inline object call `check_object`.
+    fn reference<T, F>(&mut self, location: &str, reference: &'s Reference<T>, check_object: F)
+    where
+        T: Component,
+        F: FnOnce(&mut Self, &str, &'s T),
+    {
+        match (&reference.r#ref, &reference.object) {
+            (Some(r), _) => self.check::<T>(location, r),
+            (None, Some(object)) => check_object(self, location, object),
+            (None, None) => {}
+        }
+    }
+
+    fn spec(&mut self) {
+        let spec = self.spec;
+        for (path, path_item) in &spec.paths {
+            self.path_item(&join("/paths", path), path_item);
+        }
+        for (name, path_item) in &spec.webhooks {
+            self.path_item(&join("/webhooks", name), path_item);
+        }
+
+        let components = &spec.components;
+        for (name, schema) in &components.schemas {
+            self.schema(&join("/components/schemas", name), schema);
+        }
+        for (name, response) in &components.responses {
+            self.reference(
+                &join("/components/responses", name),
+                response,
+                Self::response,
+            );
+        }
+        for (name, parameter) in &components.parameters {
+            self.reference(
+                &join("/components/parameters", name),
+                parameter,
+                Self::parameter,
+            );
+        }
+        for (name, example) in &components.examples {
+            self.reference(&join("/components/examples", name), example, |_, _, _| {});
+        }
+        for (name, body) in &components.request_bodies {
+            self.reference(
+                &join("/components/requestBodies", name),
+                body,
+                Self::request_body,
+            );
+        }
+        for (name, header) in &components.headers {
+            self.reference(&join("/components/headers", name), header, Self::header);
+        }
+        for (name, scheme) in &components.security_schemes {
+            let location = join("/components/securitySchemes", name);
+            self.reference(&location, scheme, |_, _, _| {});
+        }
+        for (name, link) in &components.links {
+            self.reference(&join("/components/links", name), link, |_, _, _| {});
+        }
+        for (name, callback) in &components.callbacks {
+            self.reference(
+                &join("/components/callbacks", name),
+                callback,
+                Self::callback,
+            );
+        }
+        for (name, path_item) in &components.path_items {
+            self.path_item(&join("/components/pathItems", name), path_item);
+        }
+    }
+
+    fn path_item(&mut self, location: &str, path_item: &'s PathItem) {
+        if let Some(reference) = &path_item.r#ref {
+            self.check::<PathItem>(location, reference);
+        }
+        self.parameters(location, &path_item.parameters);
+        for (method, operation) in path_item.operations() {
+            self.operation(&join(location, method.as_str()), operation);
+        }
+    }
+
+    fn operation(&mut self, location: &str, operation: &'s Operation) {
+        self.parameters(location, &operation.parameters);
+        if let Some(body) = &operation.request_body {
+            self.reference(&join(location, "requestBody"), body, Self::request_body);
+        }
+        if let Some(responses) = &operation.responses {
+            let location = join(location, "responses");
+            if let Some(response) = &responses.default {
+                self.reference(&join(&location, "default"), response, Self::response);
+            }
+            for (code, response) in &responses.response {
+                self.reference(&join(&location, code), response, Self::response);
+            }
+        }
+        for (name, callback) in &operation.callbacks {
+            let location = join(&join(location, "callbacks"), name);
+            self.reference(&location, callback, Self::callback);
+        }
+    }
+
+    fn parameters(&mut self, location: &str, parameters: &'s [Reference<Parameter>]) {
+        let location = join(location, "parameters");
+        for (i, parameter) in parameters.iter().enumerate() {
+            self.reference(&join(&location, &i.to_string()), parameter, Self::parameter);
+        }
+    }
+
+    fn parameter(&mut self, location: &str, parameter: &'s Parameter) {
+        if let Some(schema) = &parameter.schema {
+            self.schema(&join(location, "schema"), schema);
+        }
+        self.examples(location, &parameter.examples);
+        self.content(location, &parameter.content);
+    }
+
+    fn request_body(&mut self, location: &str, body: &'s RequestBody) {
+        self.content(location, &body.content);
+    }
+
+    fn response(&mut self, location: &str, response: &'s Response) {
+        self.headers(location, &response.headers);
+        self.content(location, &response.content);
+        let links_location = join(location, "links");
+        for (name, link) in &response.links {
+            self.reference(&join(&links_location, name), link, |_, _, _| {});
+        }
+    }
+
+    fn callback(&mut self, location: &str, callback: &'s Callback) {
+        for (expression, path_item) in &callback.expressions {
+            self.path_item(&join(location, expression), path_item);
+        }
+    }
+
+    fn headers(&mut self, location: &str, headers: &'s HashMap<String, Reference<Header>>) {
+        let location = join(location, "headers");
+        for (name, header) in headers {
+            self.reference(&join(&location, name), header, Self::header);
+        }
+    }
+
+    fn header(&mut self, location: &str, header: &'s Header) {
+        if let Some(schema) = &header.schema {
+            self.schema(&join(location, "schema"), schema);
+        }
+        self.examples(location, &header.examples);
+        self.content(location, &header.content);
+    }
+
+    fn examples(&mut self, location: &str, examples: &'s HashMap<String, Reference<Example>>) {
+        let location = join(location, "examples");
+        for (name, example) in examples {
+            self.reference(&join(&location, name), example, |_, _, _| {});
+        }
+    }
+
+    fn content(&mut self, location: &str, content: &'s HashMap<String, MediaType>) {
+        let location = join(location, "content");
+        for (media_type, content) in content {
+            let location = join(&location, media_type);
+            if let Some(schema) = &content.schema {
+                self.schema(&join(&location, "schema"), schema);
+            }
+            self.examples(&location, &content.examples);
+            let encoding_location = join(&location, "encoding");
+            for (name, encoding) in &content.encoding {
+                self.headers(&join(&encoding_location, name), &encoding.headers);
+            }
+        }
+    }
+
+    fn schema(&mut self, location: &str, schema: &'s Schema) {
+        if let Some(reference) = &schema.r#ref {
+            // Schemas can reference (sub)schemas using any JSON pointer, we
+            // only check references to the components.
+            if !reference.starts_with('#') || reference.starts_with("#/components/") {
+                self.check::<Schema>(location, reference);
+            }
+        }
+        if let Some(discriminator) = &schema.discriminator {
+            let location = join(&join(location, "discriminator"), "mapping");
+            for value in discriminator.mapping.values() {
+                if !is_schema_name(value) {
+                    if !value.starts_with('#') || value.starts_with("#/components/") {
+                        self.check::<Schema>(&location, value);
+                    }
+                } else if !self.spec.components.schemas.contains_key(value) {
+                    self.broken.push(BrokenReference {
+                        location: location.clone(),
+                        reference: value.clone(),
+                        kind: BrokenReferenceKind::Missing,
+                    });
+                }
+            }
+        }
+
+        let schemas = [
+            ("not", &schema.not),
+            ("if", &schema.r#if),
+            ("then", &schema.then),
+            ("else", &schema.r#else),
+            ("items", &schema.items),
+            ("contains", &schema.contains),
+            ("propertyNames", &schema.property_names),
+            ("unevaluatedItems", &schema.unevaluated_items),
+            ("unevaluatedProperties", &schema.unevaluated_properties),
+            ("contentSchema", &schema.content_schema),
+        ];
+        for (keyword, subschema) in schemas {
+            if let Some(subschema) = subschema {
+                self.schema(&join(location, keyword), subschema);
+            }
+        }
+        if let Some(BoolOr::Item(subschema)) = &schema.additional_properties {
+            self.schema(&join(location, "additionalProperties"), subschema);
+        }
+
+        let schemas = [
+            ("allOf", &schema.all_of),
+            ("anyOf", &schema.any_of),
+            ("oneOf", &schema.one_of),
+        ];
+        for (keyword, subschemas) in schemas {
+            if let Some(subschemas) = subschemas {
+                self.schemas(&join(location, keyword), subschemas);
+            }
+        }
+        self.schemas(&join(location, "prefixItems"), &schema.prefix_items);
+
+        if let Some(properties) = &schema.properties {
+            let location = join(location, "properties");
+            for (name, subschema) in properties {
+                self.schema(&join(&location, name), subschema);
+            }
+        }
+        let schemas = [
+            ("patternProperties", &schema.pattern_properties),
+            ("dependentSchemas", &schema.dependent_schemas),
+            ("$defs", &schema.defs),
+        ];
+        for (keyword, subschemas) in schemas {
+            let location = join(location, keyword);
+            for (name, subschema) in subschemas {
+                self.schema(&join(&location, name), subschema);
+            }
+        }
+    }
+
+    fn schemas(&mut self, location: &str, schemas: &'s [Schema]) {
+        for (i, schema) in schemas.iter().enumerate() {
+            self.schema(&join(location, &i.to_string()), schema);
+        }
+    }
+}
+
+/// Join JSON pointer `location` with `token`.
+fn join(location: &str, token: &str) -> String {
+    format!("{}/{}", location, escape(token))
+}
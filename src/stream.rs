@@ -0,0 +1,83 @@
+//! Module with streaming parsing of large specifications.
+
+use std::fmt;
+use std::io::{self, BufReader, Read};
+
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, Visitor};
+
+use crate::{ParseError, PathItem};
+
+/// Read the [`Spec::paths`] of a JSON [Open API Specification] from `reader`,
+/// calling `f` for each path and path item.
+///
+/// Unlike [`read_from_json_reader`] this doesn't keep the entire document in
+/// memory, only a single path item at a time. This is useful for very large
+/// specifications. All fields other than `paths` are skipped, use a second
+/// pass if they're needed as well.
+///
+/// Note that this is only supported for JSON as YAML documents have to be
+/// loaded entirely to resolve aliases.
+///
+/// [`Spec::paths`]: crate::Spec::paths
+/// [Open API Specification]: crate::Spec
+/// [`read_from_json_reader`]: crate::read_from_json_reader
+pub fn stream_json_paths<R, F>(reader: R, mut f: F) -> io::Result<()>
+where
+    R: Read,
+    F: FnMut(String, PathItem),
+{
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(reader));
+    deserializer
+        .deserialize_map(SpecVisitor(&mut f))
+        .and_then(|()| deserializer.end())
+        .map_err(|err| ParseError::Json(err).into())
+}
+
+/// Visits the top-level object, only deserialising `paths`.
+struct SpecVisitor<'f, F>(&'f mut F);
+
+impl<'de, F: FnMut(String, PathItem)> Visitor<'de> for SpecVisitor<'_, F> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an OpenAPI object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "paths" {
+                map.next_value_seed(Paths(&mut *self.0))?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Deserialises the `paths` object, calling the function for each path.
+struct Paths<'f, F>(&'f mut F);
+
+impl<'de, F: FnMut(String, PathItem)> DeserializeSeed<'de> for Paths<'_, F> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F: FnMut(String, PathItem)> Visitor<'de> for Paths<'_, F> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a paths object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(path) = map.next_key::<String>()? {
+            let path_item = map.next_value()?;
+            (self.0)(path, path_item);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+//! Module with URL template expansion.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::{ParameterStyle, Server};
+
+impl Server {
+    /// Returns the [`url`] with all variables substituted.
+    ///
+    /// The value for a variable is taken from `overrides`, falling back to the
+    /// [`default`] of the variable. Values are checked against the [`enum`] of
+    /// the variable, if not empty.
+    ///
+    /// [`url`]: Server::url
+    /// [`default`]: crate::ServerVariable::default
+    /// [`enum`]: crate::ServerVariable::enum
+    pub fn resolve_url(&self, overrides: &HashMap<String, String>) -> Result<String, ServerError> {
+        if let Some(name) = overrides
+            .keys()
+            .find(|name| !self.variables.contains_key(*name))
+        {
+            return Err(ServerError::UnknownVariable(name.clone()));
+        }
+
+        let mut url = String::with_capacity(self.url.len());
+        let mut rest = &*self.url;
+        while let Some(start) = rest.find('{') {
+            url.push_str(&rest[..start]);
+            let end = match rest[start..].find('}') {
+                Some(end) => start + end,
+                None => return Err(ServerError::InvalidTemplate),
+            };
+            let name = &rest[start + 1..end];
+            let variable = self
+                .variables
+                .get(name)
+                .ok_or_else(|| ServerError::UnknownVariable(name.to_owned()))?;
+            let value = overrides.get(name).unwrap_or(&variable.default);
+            let values = variable.r#enum.as_deref().unwrap_or_default();
+            if !values.is_empty() && !values.contains(value) {
+                return Err(ServerError::InvalidValue {
+                    variable: name.to_owned(),
+                    value: value.clone(),
+                });
+            }
+            url.push_str(value);
+            rest = &rest[end + 1..];
+        }
+        url.push_str(rest);
+        Ok(url)
+    }
+}
+
+/// Expand the path `template`, e.g. `/pets/{petId}`, using the values in
+/// `params`.
+///
+/// The style of each parameter is taken from `styles`, defaulting to
+/// [`ParameterStyle::Simple`] (the default for path parameters). Only the
+/// styles for path parameters are supported: [`ParameterStyle::Simple`],
+/// [`ParameterStyle::Label`] and [`ParameterStyle::Matrix`]. Values are
+/// percent-encoded.
+pub fn expand_path(
+    template: &str,
+    params: &HashMap<String, String>,
+    styles: &HashMap<String, ParameterStyle>,
+) -> Result<String, PathError> {
+    let mut path = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        path.push_str(&rest[..start]);
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => return Err(PathError::InvalidTemplate),
+        };
+        let name = &rest[start + 1..end];
+        let value = params
+            .get(name)
+            .ok_or_else(|| PathError::MissingParameter(name.to_owned()))?;
+        match styles.get(name).unwrap_or(&ParameterStyle::Simple) {
+            ParameterStyle::Simple => {}
+            ParameterStyle::Label => path.push('.'),
+            ParameterStyle::Matrix => {
+                path.push(';');
+                percent_encode(&mut path, name);
+                path.push('=');
+            }
+            _ => return Err(PathError::UnsupportedStyle(name.to_owned())),
+        }
+        percent_encode(&mut path, value);
+        rest = &rest[end + 1..];
+    }
+    path.push_str(rest);
+    Ok(path)
+}
+
+/// Percent-encodes all characters in `value`, except the unreserved
+/// characters as defined by [RFC3986].
+///
+/// [RFC3986]: https://tools.ietf.org/html/rfc3986#section-2.3
+fn percent_encode(output: &mut String, value: &str) {
+    for b in value.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+            output.push(b as char);
+        } else {
+            output.push_str(&format!("%{:02X}", b));
+        }
+    }
+}
+
+/// Error returned by [`Server::resolve_url`].
+#[derive(Debug)]
+pub enum ServerError {
+    /// The URL contains an unclosed `{`.
+    InvalidTemplate,
+    /// The variable is not defined in [`Server::variables`].
+    UnknownVariable(String),
+    /// The value is not one of the [`enum`] values of the variable.
+    ///
+    /// [`enum`]: crate::ServerVariable::enum
+    InvalidValue {
+        /// Name of the variable.
+        variable: String,
+        /// The invalid value.
+        value: String,
+    },
+    /// The URL is invalid, returned by [`Server::parsed_url`].
+    #[cfg(feature = "url")]
+    InvalidUrl(url::ParseError),
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServerError::InvalidTemplate => f.write_str("invalid server URL: unclosed `{`"),
+            ServerError::UnknownVariable(name) => write!(f, "unknown server variable `{}`", name),
+            ServerError::InvalidValue { variable, value } => write!(
+                f,
+                "invalid value `{}` for server variable `{}`",
+                value, variable
+            ),
+            #[cfg(feature = "url")]
+            ServerError::InvalidUrl(err) => write!(f, "invalid server URL: {}", err),
+        }
+    }
+}
+
+impl Error for ServerError {}
+
+/// Error returned by [`expand_path`].
+#[derive(Debug)]
+pub enum PathError {
+    /// The path contains an unclosed `{`.
+    InvalidTemplate,
+    /// No value was provided for the parameter.
+    MissingParameter(String),
+    /// The style of the parameter is not supported for path parameters.
+    UnsupportedStyle(String),
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathError::InvalidTemplate => f.write_str("invalid path: unclosed `{`"),
+            PathError::MissingParameter(name) => write!(f, "missing path parameter `{}`", name),
+            PathError::UnsupportedStyle(name) => {
+                write!(f, "unsupported style for path parameter `{}`", name)
+            }
+        }
+    }
+}
+
+impl Error for PathError {}
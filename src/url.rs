@@ -0,0 +1,93 @@
+//! Module with parsing of URL fields.
+
+use std::collections::HashMap;
+
+use url::{ParseError, Url};
+
+use crate::{
+    Contact, ExternalDocument, Info, License, OauthFlow, SecurityScheme, Server, ServerError,
+};
+
+impl Server {
+    /// Returns the parsed [`url`], with all variables substituted using their
+    /// default value.
+    ///
+    /// Note that the URL of a server MAY be relative, which results in a
+    /// [`ServerError::InvalidUrl`] error containing
+    /// [`ParseError::RelativeUrlWithoutBase`]. Use [`Server::resolve_url`] and
+    /// [`Url::join`] to resolve those.
+    ///
+    /// [`url`]: Server::url
+    pub fn parsed_url(&self) -> Result<Url, ServerError> {
+        let url = self.resolve_url(&HashMap::new())?;
+        Url::parse(&url).map_err(ServerError::InvalidUrl)
+    }
+}
+
+impl Info {
+    /// Returns the parsed [`terms_of_service`], if any.
+    ///
+    /// [`terms_of_service`]: Info::terms_of_service
+    pub fn parsed_terms_of_service(&self) -> Option<Result<Url, ParseError>> {
+        self.terms_of_service.as_deref().map(Url::parse)
+    }
+}
+
+impl Contact {
+    /// Returns the parsed [`url`], if any.
+    ///
+    /// [`url`]: Contact::url
+    pub fn parsed_url(&self) -> Option<Result<Url, ParseError>> {
+        self.url.as_deref().map(Url::parse)
+    }
+}
+
+impl License {
+    /// Returns the parsed [`url`], if any.
+    ///
+    /// [`url`]: License::url
+    pub fn parsed_url(&self) -> Option<Result<Url, ParseError>> {
+        self.url.as_deref().map(Url::parse)
+    }
+}
+
+impl ExternalDocument {
+    /// Returns the parsed [`url`].
+    ///
+    /// [`url`]: ExternalDocument::url
+    pub fn parsed_url(&self) -> Result<Url, ParseError> {
+        Url::parse(&self.url)
+    }
+}
+
+impl SecurityScheme {
+    /// Returns the parsed [`open_id_connect_url`], if any.
+    ///
+    /// [`open_id_connect_url`]: SecurityScheme::open_id_connect_url
+    pub fn parsed_open_id_connect_url(&self) -> Option<Result<Url, ParseError>> {
+        self.open_id_connect_url.as_deref().map(Url::parse)
+    }
+}
+
+impl OauthFlow {
+    /// Returns the parsed [`authorization_url`].
+    ///
+    /// [`authorization_url`]: OauthFlow::authorization_url
+    pub fn parsed_authorization_url(&self) -> Result<Url, ParseError> {
+        Url::parse(&self.authorization_url)
+    }
+
+    /// Returns the parsed [`token_url`].
+    ///
+    /// [`token_url`]: OauthFlow::token_url
+    pub fn parsed_token_url(&self) -> Result<Url, ParseError> {
+        Url::parse(&self.token_url)
+    }
+
+    /// Returns the parsed [`refresh_url`], if any.
+    ///
+    /// [`refresh_url`]: OauthFlow::refresh_url
+    pub fn parsed_refresh_url(&self) -> Option<Result<Url, ParseError>> {
+        self.refresh_url.as_deref().map(Url::parse)
+    }
+}
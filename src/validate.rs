@@ -0,0 +1,766 @@
+//! Module with spec validation.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::{
+    is_supported_dialect, Example, Header, HttpMethod, Link, MediaType, Operation, Parameter,
+    ParameterLocation, ParameterStyle, PathItem, Reference, Response, Schema, SecurityRequirement,
+    SecurityScheme, SecuritySchemeType, Server, Spec, Visitor,
+};
+#[cfg(feature = "url")]
+use crate::{ExternalDocument, ServerError};
+
+/// Error returned by [`Spec::validate`].
+#[derive(Debug)]
+pub struct ValidationError {
+    /// Location of the error in the document as JSON pointer, e.g.
+    /// `/paths/~1pets/get/responses`.
+    pub location: String,
+    /// Description of the error.
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+impl Error for ValidationError {}
+
+impl Spec {
+    /// Validate the specification.
+    ///
+    /// This checks the rules of the OpenAPI specification that can't be
+    /// enforced by the types alone, such as:
+    ///  * All path templates (e.g. `{petId}`) have a matching required path
+    ///    [`Parameter`].
+    ///  * All [`Responses`] contain at least one response.
+    ///  * All [`Operation::operation_id`]s are unique.
+    ///  * [`License::identifier`] and [`License::url`] are mutually exclusive.
+    ///  * [`Contact::email`] looks like an email address.
+    ///  * All tag names in [`Spec::tags`] are unique.
+    ///  * All names of the [`Components`] match `^[a-zA-Z0-9._-]+$`.
+    ///  * The `enum` of all [`ServerVariable`]s is not empty and the default
+    ///    is one of its values.
+    ///  * All inline [`Parameter`]s are valid, see [`Parameter::validate`].
+    ///  * The `example` and `examples` fields of a [`Header`] or
+    ///    [`MediaType`] are mutually exclusive, as are [`Example::value`] and
+    ///    [`Example::external_value`].
+    ///  * All inline [`SecurityScheme`]s are valid, see
+    ///    [`SecurityScheme::validate`].
+    ///  * All [`SecurityRequirement`]s refer to a declared [`SecurityScheme`].
+    ///  * No `Content-Type` header is defined in a [`Response`] or
+    ///    [`Encoding`], as it's ignored.
+    ///  * All URL fields, such as [`Server::url`], contain a valid URL
+    ///    (requires the `url` feature). Server URLs may be relative.
+    ///  * [`Spec::json_schema_dialect`] and [`Schema::schema`] refer to a
+    ///    supported dialect, i.e. JSON Schema Draft 2020-12 or the OpenAPI
+    ///    base dialect, see [`Spec::schema_dialect`].
+    ///  * All regular expressions in [`Schema::pattern`] and
+    ///    [`Schema::pattern_properties`] compile (requires the `regex`
+    ///    feature).
+    ///
+    /// [`Response`]: crate::Response
+    /// [`Components`]: crate::Components
+    /// [`Server::url`]: crate::Server::url
+    /// [`ServerVariable`]: crate::ServerVariable
+    /// [`Encoding`]: crate::Encoding
+    /// [`Schema::schema`]: crate::Schema::schema
+    /// [`Schema::pattern`]: crate::Schema::pattern
+    /// [`Schema::pattern_properties`]: crate::Schema::pattern_properties
+    /// [`Responses`]: crate::Responses
+    /// [`Contact::email`]: crate::Contact::email
+    /// [`License::identifier`]: crate::License::identifier
+    /// [`License::url`]: crate::License::url
+    /// [`SecurityRequirement`]: crate::SecurityRequirement
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut validator = Validator {
+            spec: self,
+            errors: Vec::new(),
+            operation_ids: HashMap::new(),
+        };
+        validator.validate_spec();
+        self.walk(&mut validator);
+        if validator.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(validator.errors)
+        }
+    }
+}
+
+struct Validator<'s> {
+    spec: &'s Spec,
+    errors: Vec<ValidationError>,
+    /// Operation id -> location of the operation.
+    operation_ids: HashMap<&'s str, String>,
+}
+
+impl<'s> Validator<'s> {
+    fn error<M: Into<String>>(&mut self, location: String, message: M) {
+        self.errors.push(ValidationError {
+            location,
+            message: message.into(),
+        });
+    }
+
+    fn validate_spec(&mut self) {
+        if let Some(email) = self
+            .spec
+            .info
+            .contact
+            .as_ref()
+            .and_then(|c| c.email.as_ref())
+        {
+            if !is_email(email) {
+                self.error("/info/contact/email".to_owned(), "invalid email address");
+            }
+        }
+        if let Some(license) = &self.spec.info.license {
+            if license.identifier.is_some() && license.url.is_some() {
+                self.error(
+                    "/info/license".to_owned(),
+                    "`identifier` and `url` are mutually exclusive",
+                );
+            }
+        }
+        for (i, tag) in self.spec.tags.iter().enumerate() {
+            let first = self.spec.tags.iter().position(|t| t.name == tag.name);
+            if let Some(first) = first.filter(|first| *first != i) {
+                self.error(
+                    format!("/tags/{}/name", i),
+                    format!(
+                        "duplicate tag name `{}`, also used in /tags/{}",
+                        tag.name, first
+                    ),
+                );
+            }
+        }
+
+        if let Some(dialect) = &self.spec.json_schema_dialect {
+            self.validate_dialect("/jsonSchemaDialect".to_owned(), dialect);
+        }
+        self.validate_component_names();
+
+        for (name, parameter) in &self.spec.components.parameters {
+            if let Some(parameter) = &parameter.object {
+                let location = format!("/components/parameters/{}", escape(name));
+                validate_parameter(&location, parameter, &mut self.errors);
+            }
+        }
+
+        for (name, security_scheme) in &self.spec.components.security_schemes {
+            if let Some(security_scheme) = &security_scheme.object {
+                let location = format!("/components/securitySchemes/{}", escape(name));
+                validate_security_scheme(&location, security_scheme, &mut self.errors);
+            }
+        }
+        self.validate_security("", &self.spec.security);
+        #[cfg(feature = "url")]
+        self.validate_spec_urls();
+        self.validate_servers("", &self.spec.servers);
+
+        for (path, path_item) in &self.spec.paths {
+            let location = format!("/paths/{}", escape(path));
+            let path_item = path_item.resolve(self.spec);
+            self.validate_path_item(&location, Some(path), path_item);
+        }
+        for (name, path_item) in &self.spec.webhooks {
+            let location = format!("/webhooks/{}", escape(name));
+            let path_item = path_item.resolve(self.spec);
+            self.validate_path_item(&location, None, path_item);
+        }
+    }
+
+    /// If `path` is not `None` the path parameters are checked.
+    fn validate_path_item(&mut self, location: &str, path: Option<&str>, path_item: &'s PathItem) {
+        self.validate_parameters(location, &path_item.parameters);
+        for (method, operation) in path_item.operations() {
+            let location = format!("{}/{}", location, method);
+            if let Some(path) = path {
+                self.validate_path_parameters(&location, path, path_item, operation);
+            }
+            self.validate_operation(location, operation);
+        }
+    }
+
+    fn validate_path_parameters(
+        &mut self,
+        location: &str,
+        path: &str,
+        path_item: &PathItem,
+        operation: &Operation,
+    ) {
+        let spec = self.spec;
+        let parameters: Vec<&Parameter> = path_item
+            .parameters
+            .iter()
+            .chain(operation.parameters.iter())
+            .filter_map(|parameter| parameter.resolve(spec))
+            .filter(|parameter| parameter.r#in == ParameterLocation::Path)
+            .collect();
+        for name in path_templates(path) {
+            // Whether or not the parameter is required is checked by
+            // `validate_parameter`.
+            if !parameters.iter().any(|p| p.name == name) {
+                self.error(
+                    format!("{}/parameters", location),
+                    format!("missing path parameter `{}`", name),
+                );
+            }
+        }
+    }
+
+    /// Validates the inline parameters, referenced parameters are validated
+    /// as part of the components.
+    fn validate_parameters(&mut self, location: &str, parameters: &[Reference<Parameter>]) {
+        for (i, parameter) in parameters.iter().enumerate() {
+            if let Some(parameter) = &parameter.object {
+                let location = format!("{}/parameters/{}", location, i);
+                validate_parameter(&location, parameter, &mut self.errors);
+            }
+        }
+    }
+
+    /// Validates that all security requirements refer to a declared security
+    /// scheme.
+    fn validate_security(&mut self, location: &str, security: &[SecurityRequirement]) {
+        for (i, requirement) in security.iter().enumerate() {
+            for name in requirement.keys() {
+                if !self.spec.components.security_schemes.contains_key(name) {
+                    self.error(
+                        format!("{}/security/{}/{}", location, i, escape(name)),
+                        format!("undeclared security scheme `{}`", name),
+                    );
+                }
+            }
+        }
+    }
+
+    fn validate_operation(&mut self, location: String, operation: &'s Operation) {
+        self.validate_parameters(&location, &operation.parameters);
+        self.validate_security(&location, &operation.security);
+        if let Some(responses) = &operation.responses {
+            if responses.default.is_none() && responses.response.is_empty() {
+                self.error(
+                    format!("{}/responses", location),
+                    "must contain at least one response",
+                );
+            }
+        }
+
+        if let Some(id) = operation.operation_id.as_deref() {
+            if let Some(other) = self.operation_ids.get(id) {
+                let message = format!("duplicate operationId `{}`, also used in {}", id, other);
+                self.error(format!("{}/operationId", location), message);
+            } else {
+                self.operation_ids.insert(id, location);
+            }
+        }
+    }
+}
+
+impl<'s> Visitor<'s> for Validator<'s> {
+    fn visit_path_item(&mut self, location: &str, path_item: &'s PathItem) {
+        self.validate_servers(location, &path_item.servers);
+    }
+
+    fn visit_operation(&mut self, location: &str, _: HttpMethod, operation: &'s Operation) {
+        self.validate_servers(location, &operation.servers);
+        #[cfg(feature = "url")]
+        self.validate_external_docs(location, operation.external_docs.as_ref());
+    }
+
+    fn visit_response(&mut self, location: &str, response: &'s Response) {
+        self.validate_headers(location, &response.headers);
+    }
+
+    fn visit_header(&mut self, location: &str, header: &'s Header) {
+        if header.example.is_some() && !header.examples.is_empty() {
+            self.error(
+                format!("{}/example", location),
+                "`example` and `examples` are mutually exclusive",
+            );
+        }
+    }
+
+    fn visit_media_type(&mut self, location: &str, media_type: &'s MediaType) {
+        if media_type.example.is_some() && !media_type.examples.is_empty() {
+            self.error(
+                format!("{}/example", location),
+                "`example` and `examples` are mutually exclusive",
+            );
+        }
+        for (name, encoding) in &media_type.encoding {
+            let location = format!("{}/encoding/{}", location, escape(name));
+            self.validate_headers(&location, &encoding.headers);
+        }
+    }
+
+    fn visit_example(&mut self, location: &str, example: &'s Example) {
+        if example.value.is_some() && example.external_value.is_some() {
+            self.error(
+                location.to_owned(),
+                "`value` and `externalValue` are mutually exclusive",
+            );
+        }
+    }
+
+    fn visit_link(&mut self, location: &str, link: &'s Link) {
+        if let Some(server) = &link.server {
+            self.validate_server(format!("{}/server", location), server);
+        }
+    }
+
+    #[cfg(feature = "url")]
+    fn visit_security_scheme(&mut self, location: &str, security_scheme: &'s SecurityScheme) {
+        if let Some(url) = &security_scheme.open_id_connect_url {
+            self.validate_url(format!("{}/openIdConnectUrl", location), url);
+        }
+        if let Some(flows) = &security_scheme.flows {
+            let location = format!("{}/flows", location);
+            let flows = [
+                ("implicit", &flows.implicit, true, false),
+                ("password", &flows.password, false, true),
+                ("clientCredentials", &flows.client_credentials, false, true),
+                ("authorizationCode", &flows.authorization_code, true, true),
+            ];
+            for (name, flow, authorization, token) in flows {
+                let flow = match flow {
+                    Some(flow) => flow,
+                    None => continue,
+                };
+                let location = format!("{}/{}", location, name);
+                // Only validate the URLs that apply to the flow.
+                if authorization {
+                    let url = &flow.authorization_url;
+                    self.validate_url(format!("{}/authorizationUrl", location), url);
+                }
+                if token {
+                    self.validate_url(format!("{}/tokenUrl", location), &flow.token_url);
+                }
+                if let Some(url) = &flow.refresh_url {
+                    self.validate_url(format!("{}/refreshUrl", location), url);
+                }
+            }
+        }
+    }
+
+    fn visit_schema(&mut self, location: &str, schema: &'s Schema) {
+        if let Some(dialect) = &schema.schema {
+            self.validate_dialect(format!("{}/$schema", location), dialect);
+        }
+        #[cfg(feature = "url")]
+        self.validate_external_docs(location, schema.external_docs.as_ref());
+        #[cfg(feature = "regex")]
+        self.validate_patterns(location, schema);
+    }
+}
+
+/// Validation of regular expressions.
+#[cfg(feature = "regex")]
+impl Validator<'_> {
+    /// Validates that [`Schema::pattern`] and the keys of
+    /// [`Schema::pattern_properties`] compile, see [`Schema::compiled_pattern`].
+    fn validate_patterns(&mut self, location: &str, schema: &Schema) {
+        if let Some(Err(err)) = schema.compiled_pattern() {
+            self.error(
+                format!("{}/pattern", location),
+                format!("invalid regular expression: {}", err),
+            );
+        }
+        for pattern in schema.pattern_properties.keys() {
+            if let Err(err) = regex::Regex::new(pattern) {
+                self.error(
+                    format!("{}/patternProperties/{}", location, escape(pattern)),
+                    format!("invalid regular expression: {}", err),
+                );
+            }
+        }
+    }
+}
+
+/// Validation of the URL fields.
+#[cfg(feature = "url")]
+impl Validator<'_> {
+    fn validate_spec_urls(&mut self) {
+        let info = &self.spec.info;
+        if let Some(url) = &info.terms_of_service {
+            self.validate_url("/info/termsOfService".to_owned(), url);
+        }
+        if let Some(url) = info.contact.as_ref().and_then(|c| c.url.as_ref()) {
+            self.validate_url("/info/contact/url".to_owned(), url);
+        }
+        if let Some(url) = info.license.as_ref().and_then(|l| l.url.as_ref()) {
+            self.validate_url("/info/license/url".to_owned(), url);
+        }
+        self.validate_external_docs("", self.spec.external_docs.as_ref());
+        for (i, tag) in self.spec.tags.iter().enumerate() {
+            let location = format!("/tags/{}", i);
+            self.validate_external_docs(&location, tag.external_docs.as_ref());
+        }
+    }
+
+    /// Validates the URL of `server`, which MAY be relative.
+    fn validate_server_url(&mut self, location: String, server: &Server) {
+        let url = match server.resolve_url(&HashMap::new()) {
+            Ok(url) => url,
+            // Already reported by `validate_server`.
+            Err(ServerError::InvalidValue { .. }) => return,
+            Err(err) => return self.error(format!("{}/url", location), err.to_string()),
+        };
+        let base = url::Url::parse("http://localhost").unwrap();
+        if let Err(err) = url::Url::options().base_url(Some(&base)).parse(&url) {
+            self.error(format!("{}/url", location), format!("invalid URL: {}", err));
+        }
+    }
+
+    fn validate_external_docs(&mut self, location: &str, docs: Option<&ExternalDocument>) {
+        if let Some(docs) = docs {
+            self.validate_url(format!("{}/externalDocs/url", location), &docs.url);
+        }
+    }
+
+    fn validate_url(&mut self, location: String, url: &str) {
+        if let Err(err) = url::Url::parse(url) {
+            self.error(location, format!("invalid URL: {}", err));
+        }
+    }
+}
+
+impl Validator<'_> {
+    /// Validates that no `Content-Type` header is defined, which must be
+    /// ignored.
+    fn validate_headers(&mut self, location: &str, headers: &HashMap<String, Reference<Header>>) {
+        for name in headers.keys() {
+            if name.eq_ignore_ascii_case("Content-Type") {
+                self.error(
+                    format!("{}/headers/{}", location, escape(name)),
+                    "`Content-Type` header is ignored",
+                );
+            }
+        }
+    }
+
+    /// Validates that the JSON Schema `dialect` is supported, schemas of other
+    /// dialects would be interpreted as Draft 2020-12.
+    fn validate_dialect(&mut self, location: String, dialect: &str) {
+        if !is_supported_dialect(dialect) {
+            self.error(
+                location,
+                format!("unsupported JSON Schema dialect `{}`", dialect),
+            );
+        }
+    }
+
+    /// Validates that all component names match `^[a-zA-Z0-9._-]+$`, otherwise
+    /// they can't be referenced.
+    fn validate_component_names(&mut self) {
+        let components = &self.spec.components;
+        macro_rules! check {
+            ($( $field: ident => $name: expr ),* $(,)?) => {
+                $(
+                for name in components.$field.keys() {
+                    if !is_component_name(name) {
+                        self.error(
+                            format!("/components/{}/{}", $name, escape(name)),
+                            format!(
+                                "invalid component name `{}`, must match `^[a-zA-Z0-9._-]+$`",
+                                name
+                            ),
+                        );
+                    }
+                }
+                )*
+            };
+        }
+        check!(
+            schemas => "schemas",
+            responses => "responses",
+            parameters => "parameters",
+            examples => "examples",
+            request_bodies => "requestBodies",
+            headers => "headers",
+            security_schemes => "securitySchemes",
+            links => "links",
+            callbacks => "callbacks",
+            path_items => "pathItems",
+        );
+    }
+
+    fn validate_servers(&mut self, location: &str, servers: &[Server]) {
+        for (i, server) in servers.iter().enumerate() {
+            self.validate_server(format!("{}/servers/{}", location, i), server);
+        }
+    }
+
+    /// Validates that the `enum` of all variables is not empty and that the
+    /// default is one of the allowed values.
+    fn validate_server(&mut self, location: String, server: &Server) {
+        for (name, variable) in &server.variables {
+            let location = format!("{}/variables/{}", location, escape(name));
+            match &variable.r#enum {
+                Some(values) if values.is_empty() => {
+                    self.error(
+                        format!("{}/enum", location),
+                        "must contain at least one value",
+                    );
+                }
+                Some(values) if !values.contains(&variable.default) => {
+                    self.error(
+                        format!("{}/default", location),
+                        format!(
+                            "default `{}` is not one of the `enum` values",
+                            variable.default
+                        ),
+                    );
+                }
+                _ => {}
+            }
+        }
+        #[cfg(feature = "url")]
+        self.validate_server_url(location, server);
+    }
+}
+
+impl Parameter {
+    /// Validate the combination of fields of the parameter.
+    ///
+    /// This checks that:
+    ///  * [`Parameter::required`] is `true` for path parameters.
+    ///  * [`Parameter::allow_empty_value`] and [`Parameter::allow_reserved`]
+    ///    are only used for query parameters.
+    ///  * [`Parameter::style`] is allowed for the location of the parameter.
+    ///  * Either [`Parameter::schema`] or [`Parameter::content`] is set, but
+    ///    not both.
+    ///  * [`Parameter::example`] and [`Parameter::examples`] are not both set.
+    ///
+    /// The location of the errors is relative to the parameter, e.g.
+    /// `/required`.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        validate_parameter("", self, &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn validate_parameter(location: &str, parameter: &Parameter, errors: &mut Vec<ValidationError>) {
+    let mut error = |field: &str, message: String| {
+        errors.push(ValidationError {
+            location: format!("{}/{}", location, field),
+            message,
+        });
+    };
+    let name = &parameter.name;
+    let r#in = &parameter.r#in;
+
+    if *r#in == ParameterLocation::Path && !parameter.required {
+        error(
+            "required",
+            format!("path parameter `{}` must be required", name),
+        );
+    }
+    if *r#in != ParameterLocation::Query {
+        if parameter.allow_empty_value {
+            error(
+                "allowEmptyValue",
+                format!("only allowed for query parameters, not `{}`", name),
+            );
+        }
+        if parameter.allow_reserved {
+            error(
+                "allowReserved",
+                format!("only allowed for query parameters, not `{}`", name),
+            );
+        }
+    }
+    if let Some(style) = &parameter.style {
+        if !style_allowed(style, r#in) {
+            error(
+                "style",
+                format!(
+                    "style {:?} not allowed for parameter `{}` in {:?}",
+                    style, name, r#in
+                ),
+            );
+        }
+    }
+    match (&parameter.schema, parameter.content.is_empty()) {
+        (Some(_), false) => error(
+            "schema",
+            format!(
+                "parameter `{}` can't have both `schema` and `content`",
+                name
+            ),
+        ),
+        (None, true) => error(
+            "schema",
+            format!(
+                "parameter `{}` must have either `schema` or `content`",
+                name
+            ),
+        ),
+        _ => {}
+    }
+    if parameter.example.is_some() && !parameter.examples.is_empty() {
+        error(
+            "example",
+            "`example` and `examples` are mutually exclusive".to_owned(),
+        );
+    }
+}
+
+impl SecurityScheme {
+    /// Validate that the fields required by [`SecurityScheme::type`] are set.
+    ///
+    /// The required fields are:
+    ///  * for [`SecuritySchemeType::ApiKey`] the [`SecurityScheme::name`] and
+    ///    [`SecurityScheme::in`] fields,
+    ///  * for [`SecuritySchemeType::Http`] the [`SecurityScheme::scheme`] field,
+    ///  * for [`SecuritySchemeType::Oauth2`] the [`SecurityScheme::flows`]
+    ///    field,
+    ///  * for [`SecuritySchemeType::OpenIdConnect`] the
+    ///    [`SecurityScheme::open_id_connect_url`] field.
+    ///
+    /// The location of the errors is relative to the security scheme, e.g.
+    /// `/name`.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        validate_security_scheme("", self, &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn validate_security_scheme(
+    location: &str,
+    security_scheme: &SecurityScheme,
+    errors: &mut Vec<ValidationError>,
+) {
+    let (r#type, missing): (_, &[(&str, bool)]) = match security_scheme.r#type {
+        SecuritySchemeType::ApiKey => (
+            "apiKey",
+            &[
+                ("name", security_scheme.name.is_none()),
+                ("in", security_scheme.r#in.is_none()),
+            ],
+        ),
+        SecuritySchemeType::Http => ("http", &[("scheme", security_scheme.scheme.is_none())]),
+        SecuritySchemeType::Oauth2 => ("oauth2", &[("flows", security_scheme.flows.is_none())]),
+        SecuritySchemeType::OpenIdConnect => (
+            "openIdConnect",
+            &[(
+                "openIdConnectUrl",
+                security_scheme.open_id_connect_url.is_none(),
+            )],
+        ),
+        SecuritySchemeType::MutualTls => return,
+    };
+    for (field, _) in missing.iter().filter(|(_, missing)| *missing) {
+        errors.push(missing_security_scheme_field(location, r#type, field));
+    }
+}
+
+/// Error for the missing `field` required by security scheme type `type`.
+pub(crate) fn missing_security_scheme_field(
+    location: &str,
+    r#type: &str,
+    field: &str,
+) -> ValidationError {
+    ValidationError {
+        location: format!("{}/{}", location, field),
+        message: format!(
+            "`{}` is required for security scheme type `{}`",
+            field, r#type
+        ),
+    }
+}
+
+/// Returns `true` if `name` is a valid component name, i.e. matches
+/// `^[a-zA-Z0-9._-]+$`.
+fn is_component_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'))
+}
+
+/// Returns `true` if `email` looks like an email address, i.e. it has a
+/// non-empty local and domain part separated by `@`.
+///
+/// This is deliberately lenient, it doesn't implement RFC 5322.
+fn is_email(email: &str) -> bool {
+    match email.rsplit_once('@') {
+        Some((local, domain)) => !local.is_empty() && !domain.is_empty(),
+        None => false,
+    }
+}
+
+/// Returns `true` if `style` can be used for parameters in `location`.
+fn style_allowed(style: &ParameterStyle, location: &ParameterLocation) -> bool {
+    use ParameterLocation::*;
+    match style {
+        ParameterStyle::Matrix | ParameterStyle::Label => *location == Path,
+        ParameterStyle::Simple => matches!(location, Path | Header),
+        ParameterStyle::Form => matches!(location, Query | Cookie),
+        ParameterStyle::SpaceDelimited
+        | ParameterStyle::PipeDelimited
+        | ParameterStyle::DeepObject => *location == Query,
+    }
+}
+
+/// Returns the names of the templates in `path`, e.g. `petId` for
+/// `/pets/{petId}`.
+fn path_templates(path: &str) -> impl Iterator<Item = &str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+}
+
+/// Escape `token` for use in a JSON pointer.
+pub(crate) fn escape(token: &str) -> Cow<'_, str> {
+    if token.contains(['~', '/']) {
+        Cow::Owned(token.replace('~', "~0").replace('/', "~1"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// Reverse of [`escape`], e.g. `a/b` for `a~1b`.
+pub(crate) fn unescape(token: &str) -> Cow<'_, str> {
+    if token.contains('~') {
+        // NOTE: order matters, `~01` must become `~1`, not `/`.
+        Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// Parse the JSON `pointer` into its unescaped reference tokens, e.g.
+/// `["components", "schemas", "a/b"]` for `/components/schemas/a~1b`.
+///
+/// The empty pointer refers to the whole document and returns no tokens.
+/// Returns `None` if the pointer is invalid, i.e. doesn't start with `/`.
+pub(crate) fn parse_pointer(pointer: &str) -> Option<Vec<Cow<'_, str>>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+    Some(
+        pointer
+            .strip_prefix('/')?
+            .split('/')
+            .map(unescape)
+            .collect(),
+    )
+}
@@ -0,0 +1,443 @@
+//! Module with traversal of a [`Spec`].
+
+use crate::validate::escape;
+use crate::{
+    BoolOr, Callback, Example, Header, HttpMethod, Link, MediaType, Operation, Parameter, PathItem,
+    Reference, RequestBody, Response, Schema, SecurityScheme, Spec,
+};
+
+/// Visitor of the objects in a [`Spec`], see [`Spec::walk`].
+///
+/// All methods are called with the location of the object as JSON pointer,
+/// e.g. `/paths/~1pets/get`, and do nothing by default.
+#[allow(unused_variables)]
+pub trait Visitor<'s> {
+    /// Called for every [`PathItem`], in [`Spec::paths`], [`Spec::webhooks`],
+    /// callbacks and components.
+    fn visit_path_item(&mut self, location: &str, path_item: &'s PathItem) {}
+
+    /// Called for every [`Operation`].
+    fn visit_operation(&mut self, location: &str, method: HttpMethod, operation: &'s Operation) {}
+
+    /// Called for every inline [`Parameter`].
+    fn visit_parameter(&mut self, location: &str, parameter: &'s Parameter) {}
+
+    /// Called for every inline [`RequestBody`].
+    fn visit_request_body(&mut self, location: &str, request_body: &'s RequestBody) {}
+
+    /// Called for every inline [`Response`].
+    fn visit_response(&mut self, location: &str, response: &'s Response) {}
+
+    /// Called for every inline [`Header`].
+    fn visit_header(&mut self, location: &str, header: &'s Header) {}
+
+    /// Called for every [`MediaType`].
+    fn visit_media_type(&mut self, location: &str, media_type: &'s MediaType) {}
+
+    /// Called for every inline [`Example`].
+    fn visit_example(&mut self, location: &str, example: &'s Example) {}
+
+    /// Called for every inline [`Link`].
+    fn visit_link(&mut self, location: &str, link: &'s Link) {}
+
+    /// Called for every inline [`Callback`].
+    fn visit_callback(&mut self, location: &str, callback: &'s Callback) {}
+
+    /// Called for every inline [`SecurityScheme`].
+    fn visit_security_scheme(&mut self, location: &str, security_scheme: &'s SecurityScheme) {}
+
+    /// Called for every [`Schema`], including subschemas.
+    fn visit_schema(&mut self, location: &str, schema: &'s Schema) {}
+
+    /// Called for every reference (`$ref`), e.g. `#/components/schemas/Pet`.
+    /// The location is of the object containing the reference.
+    ///
+    /// This includes the references in [`Discriminator::mapping`](crate::Discriminator::mapping), but not the
+    /// mapping values that are schema names, e.g. `Pet`.
+    fn visit_reference(&mut self, location: &str, reference: &'s str) {}
+}
+
+/// Same as [`Visitor`], but allows the objects to be modified, see
+/// [`Spec::walk_mut`].
+#[allow(unused_variables)]
+pub trait VisitorMut {
+    /// See [`Visitor::visit_path_item`].
+    fn visit_path_item(&mut self, location: &str, path_item: &mut PathItem) {}
+
+    /// See [`Visitor::visit_operation`].
+    fn visit_operation(&mut self, location: &str, method: HttpMethod, operation: &mut Operation) {}
+
+    /// See [`Visitor::visit_parameter`].
+    fn visit_parameter(&mut self, location: &str, parameter: &mut Parameter) {}
+
+    /// See [`Visitor::visit_request_body`].
+    fn visit_request_body(&mut self, location: &str, request_body: &mut RequestBody) {}
+
+    /// See [`Visitor::visit_response`].
+    fn visit_response(&mut self, location: &str, response: &mut Response) {}
+
+    /// See [`Visitor::visit_header`].
+    fn visit_header(&mut self, location: &str, header: &mut Header) {}
+
+    /// See [`Visitor::visit_media_type`].
+    fn visit_media_type(&mut self, location: &str, media_type: &mut MediaType) {}
+
+    /// See [`Visitor::visit_example`].
+    fn visit_example(&mut self, location: &str, example: &mut Example) {}
+
+    /// See [`Visitor::visit_link`].
+    fn visit_link(&mut self, location: &str, link: &mut Link) {}
+
+    /// See [`Visitor::visit_callback`].
+    fn visit_callback(&mut self, location: &str, callback: &mut Callback) {}
+
+    /// See [`Visitor::visit_security_scheme`].
+    fn visit_security_scheme(&mut self, location: &str, security_scheme: &mut SecurityScheme) {}
+
+    /// See [`Visitor::visit_schema`].
+    fn visit_schema(&mut self, location: &str, schema: &mut Schema) {}
+
+    /// See [`Visitor::visit_reference`].
+    fn visit_reference(&mut self, location: &str, reference: &mut String) {}
+}
+
+impl Spec {
+    /// Walk all objects in the specification depth-first, calling the
+    /// relevant method on `visitor` for each object before visiting its
+    /// children.
+    ///
+    /// Objects behind references are not visited where they're referenced,
+    /// only where they're defined (e.g. in [`Spec::components`]).
+    pub fn walk<'s, V: Visitor<'s>>(&'s self, visitor: &mut V) {
+        Walker {
+            visitor,
+            location: String::new(),
+        }
+        .spec(self);
+    }
+
+    /// Same as [`Spec::walk`], but allows `visitor` to modify the objects.
+    ///
+    /// The children of an object are visited after it's modified.
+    pub fn walk_mut<V: VisitorMut>(&mut self, visitor: &mut V) {
+        WalkerMut {
+            visitor,
+            location: String::new(),
+        }
+        .spec(self);
+    }
+}
+
+/// Create the walker type `$walker`, using the `$ref` reference type, e.g.
+/// `&mut`, and `$ref_a` for references in iterators. `$iter`, `$as_ref` and
+/// `$operations` are the methods to get the children, e.g. `iter_mut`.
+macro_rules! walker {
+    (
+        $walker: ident,
+        impl[$( $generics: tt )*],
+        ref[$( $ref: tt )*],
+        ref_a[$( $ref_a: tt )*],
+        $iter: ident,
+        $as_ref: ident,
+        $operations: ident $(,)?
+    ) => {
+        struct $walker<'v, V: ?Sized> {
+            visitor: &'v mut V,
+            location: String,
+        }
+
+        impl<$( $generics )*> $walker<'_, V> {
+            /// Call `f` with `token` appended to the location.
+            fn path<F: FnOnce(&mut Self)>(&mut self, token: &str, f: F) {
+                let length = self.location.len();
+                self.location.push('/');
+                self.location.push_str(&escape(token));
+                f(self);
+                self.location.truncate(length);
+            }
+
+            fn spec(&mut self, spec: $( $ref )* Spec) {
+                self.path("paths", |w| {
+                    for (path, path_item) in spec.paths.$iter() {
+                        w.path(path, |w| w.path_item(path_item));
+                    }
+                });
+                self.path("webhooks", |w| {
+                    for (name, path_item) in spec.webhooks.$iter() {
+                        w.path(name, |w| w.path_item(path_item));
+                    }
+                });
+
+                self.path("components", |w| {
+                    w.path("schemas", |w| {
+                        for (name, schema) in spec.components.schemas.$iter() {
+                            w.path(name, |w| w.schema(schema));
+                        }
+                    });
+                    w.references("responses", spec.components.responses.$iter(), Self::response);
+                    w.references("parameters", spec.components.parameters.$iter(), Self::parameter);
+                    w.references("examples", spec.components.examples.$iter(), Self::example);
+                    let request_bodies = spec.components.request_bodies.$iter();
+                    w.references("requestBodies", request_bodies, Self::request_body);
+                    w.references("headers", spec.components.headers.$iter(), Self::header);
+                    let security_schemes = spec.components.security_schemes.$iter();
+                    w.references("securitySchemes", security_schemes, Self::security_scheme);
+                    w.references("links", spec.components.links.$iter(), Self::link);
+                    w.references("callbacks", spec.components.callbacks.$iter(), Self::callback);
+                    w.path("pathItems", |w| {
+                        for (name, path_item) in spec.components.path_items.$iter() {
+                            w.path(name, |w| w.path_item(path_item));
+                        }
+                    });
+                });
+            }
+
+            /// Walk the named `references`, using `walk` for inline objects.
+            fn references<'a, T: 'a, I>(
+                &mut self,
+                keyword: &str,
+                references: I,
+                walk: fn(&mut Self, $( $ref )* T),
+            ) where
+                I: Iterator<Item = (&'a String, $( $ref_a )* Reference<T>)>,
+            {
+                self.path(keyword, |w| {
+                    for (name, reference) in references {
+                        w.path(name, |w| w.reference(reference, walk));
+                    }
+                });
+            }
+
+            fn reference<T>(
+                &mut self,
+                reference: $( $ref )* Reference<T>,
+                walk: fn(&mut Self, $( $ref )* T),
+            ) {
+                if let Some(r) = reference.r#ref.$as_ref() {
+                    self.visitor.visit_reference(&self.location, r);
+                }
+                if let Some(object) = reference.object.$as_ref() {
+                    walk(self, object);
+                }
+            }
+
+            fn path_item(&mut self, path_item: $( $ref )* PathItem) {
+                self.visitor.visit_path_item(&self.location, path_item);
+                if let Some(r) = path_item.r#ref.$as_ref() {
+                    self.visitor.visit_reference(&self.location, r);
+                }
+                self.parameters(path_item.parameters.$iter());
+                for (method, operation) in path_item.$operations() {
+                    self.path(method.as_str(), |w| w.operation(method, operation));
+                }
+            }
+
+            fn operation(&mut self, method: HttpMethod, operation: $( $ref )* Operation) {
+                self.visitor.visit_operation(&self.location, method, operation);
+                self.parameters(operation.parameters.$iter());
+                if let Some(body) = operation.request_body.$as_ref() {
+                    self.path("requestBody", |w| w.reference(body, Self::request_body));
+                }
+                if let Some(responses) = operation.responses.$as_ref() {
+                    self.path("responses", |w| {
+                        if let Some(response) = responses.default.$as_ref() {
+                            w.path("default", |w| w.reference(response, Self::response));
+                        }
+                        for (code, response) in responses.response.$iter() {
+                            w.path(code, |w| w.reference(response, Self::response));
+                        }
+                    });
+                }
+                self.references("callbacks", operation.callbacks.$iter(), Self::callback);
+            }
+
+            fn parameters<'a, I>(&mut self, parameters: I)
+            where
+                I: Iterator<Item = $( $ref_a )* Reference<Parameter>>,
+            {
+                self.path("parameters", |w| {
+                    for (i, parameter) in parameters.enumerate() {
+                        w.path(&i.to_string(), |w| w.reference(parameter, Self::parameter));
+                    }
+                });
+            }
+
+            fn parameter(&mut self, parameter: $( $ref )* Parameter) {
+                self.visitor.visit_parameter(&self.location, parameter);
+                if let Some(schema) = parameter.schema.$as_ref() {
+                    self.path("schema", |w| w.schema(schema));
+                }
+                self.references("examples", parameter.examples.$iter(), Self::example);
+                self.content(parameter.content.$iter());
+            }
+
+            fn request_body(&mut self, request_body: $( $ref )* RequestBody) {
+                self.visitor.visit_request_body(&self.location, request_body);
+                self.content(request_body.content.$iter());
+            }
+
+            fn response(&mut self, response: $( $ref )* Response) {
+                self.visitor.visit_response(&self.location, response);
+                self.references("headers", response.headers.$iter(), Self::header);
+                self.content(response.content.$iter());
+                self.references("links", response.links.$iter(), Self::link);
+            }
+
+            fn header(&mut self, header: $( $ref )* Header) {
+                self.visitor.visit_header(&self.location, header);
+                if let Some(schema) = header.schema.$as_ref() {
+                    self.path("schema", |w| w.schema(schema));
+                }
+                self.references("examples", header.examples.$iter(), Self::example);
+                self.content(header.content.$iter());
+            }
+
+            fn content<'a, I>(&mut self, content: I)
+            where
+                I: Iterator<Item = (&'a String, $( $ref_a )* MediaType)>,
+            {
+                self.path("content", |w| {
+                    for (name, media_type) in content {
+                        w.path(name, |w| w.media_type(media_type));
+                    }
+                });
+            }
+
+            fn media_type(&mut self, media_type: $( $ref )* MediaType) {
+                self.visitor.visit_media_type(&self.location, media_type);
+                if let Some(schema) = media_type.schema.$as_ref() {
+                    self.path("schema", |w| w.schema(schema));
+                }
+                self.references("examples", media_type.examples.$iter(), Self::example);
+                self.path("encoding", |w| {
+                    for (name, encoding) in media_type.encoding.$iter() {
+                        w.path(name, |w| {
+                            w.references("headers", encoding.headers.$iter(), Self::header);
+                        });
+                    }
+                });
+            }
+
+            fn example(&mut self, example: $( $ref )* Example) {
+                self.visitor.visit_example(&self.location, example);
+            }
+
+            fn link(&mut self, link: $( $ref )* Link) {
+                self.visitor.visit_link(&self.location, link);
+            }
+
+            fn callback(&mut self, callback: $( $ref )* Callback) {
+                self.visitor.visit_callback(&self.location, callback);
+                for (expression, path_item) in callback.expressions.$iter() {
+                    self.path(expression, |w| w.path_item(path_item));
+                }
+            }
+
+            fn security_scheme(&mut self, security_scheme: $( $ref )* SecurityScheme) {
+                self.visitor.visit_security_scheme(&self.location, security_scheme);
+            }
+
+            fn schema(&mut self, schema: $( $ref )* Schema) {
+                self.visitor.visit_schema(&self.location, schema);
+                if let Some(r) = schema.r#ref.$as_ref() {
+                    self.visitor.visit_reference(&self.location, r);
+                }
+                if let Some(discriminator) = schema.discriminator.$as_ref() {
+                    self.path("discriminator", |w| w.path("mapping", |w| {
+                        for (_, r) in discriminator.mapping.$iter() {
+                            if !is_schema_name(r) {
+                                w.visitor.visit_reference(&w.location, r);
+                            }
+                        }
+                    }));
+                }
+
+                let schemas = [
+                    ("not", schema.not.$as_ref()),
+                    ("if", schema.r#if.$as_ref()),
+                    ("then", schema.then.$as_ref()),
+                    ("else", schema.r#else.$as_ref()),
+                    ("items", schema.items.$as_ref()),
+                    ("contains", schema.contains.$as_ref()),
+                    ("propertyNames", schema.property_names.$as_ref()),
+                    ("unevaluatedItems", schema.unevaluated_items.$as_ref()),
+                    ("unevaluatedProperties", schema.unevaluated_properties.$as_ref()),
+                    ("contentSchema", schema.content_schema.$as_ref()),
+                ];
+                for (keyword, subschema) in schemas {
+                    if let Some(subschema) = subschema {
+                        self.path(keyword, |w| w.schema(subschema));
+                    }
+                }
+                if let Some(BoolOr::Item(subschema)) = schema.additional_properties.$as_ref() {
+                    self.path("additionalProperties", |w| w.schema(subschema));
+                }
+                let schemas = [
+                    ("allOf", schema.all_of.$as_ref()),
+                    ("anyOf", schema.any_of.$as_ref()),
+                    ("oneOf", schema.one_of.$as_ref()),
+                ];
+                for (keyword, subschemas) in schemas {
+                    if let Some(subschemas) = subschemas {
+                        self.schemas(keyword, subschemas.$iter());
+                    }
+                }
+                self.schemas("prefixItems", schema.prefix_items.$iter());
+                if let Some(properties) = schema.properties.$as_ref() {
+                    self.named_schemas("properties", properties.$iter());
+                }
+                self.named_schemas("patternProperties", schema.pattern_properties.$iter());
+                self.named_schemas("dependentSchemas", schema.dependent_schemas.$iter());
+                self.named_schemas("$defs", schema.defs.$iter());
+            }
+
+            fn schemas<'a, I>(&mut self, keyword: &str, schemas: I)
+            where
+                I: Iterator<Item = $( $ref_a )* Schema>,
+            {
+                self.path(keyword, |w| {
+                    for (i, schema) in schemas.enumerate() {
+                        w.path(&i.to_string(), |w| w.schema(schema));
+                    }
+                });
+            }
+
+            fn named_schemas<'a, I>(&mut self, keyword: &str, schemas: I)
+            where
+                I: Iterator<Item = (&'a String, $( $ref_a )* Schema)>,
+            {
+                self.path(keyword, |w| {
+                    for (name, schema) in schemas {
+                        w.path(name, |w| w.schema(schema));
+                    }
+                });
+            }
+        }
+    };
+}
+
+walker!(
+    Walker,
+    impl['s, V: Visitor<'s> + ?Sized],
+    ref[&'s],
+    ref_a[&'s],
+    iter,
+    as_ref,
+    operations,
+);
+
+walker!(
+    WalkerMut,
+    impl[V: VisitorMut + ?Sized],
+    ref[&mut],
+    ref_a[&'a mut],
+    iter_mut,
+    as_mut,
+    operations_mut,
+);
+
+/// Returns true if `value` of [`Discriminator::mapping`](crate::Discriminator::mapping) is a schema name
+/// (e.g. `Pet`) rather than a reference (e.g. `#/components/schemas/Pet`).
+pub(crate) fn is_schema_name(value: &str) -> bool {
+    !value.contains(['#', '/'])
+}
@@ -0,0 +1,36 @@
+#![cfg(all(feature = "tokio", feature = "yaml"))]
+
+use openapi::{from_yaml_str_async, read_from_file, read_from_file_async};
+
+const PETSTORE: &str = "tests/data/petstore.yaml";
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+#[test]
+fn read() {
+    let expected = read_from_file(PETSTORE).unwrap();
+    let spec = block_on(read_from_file_async(PETSTORE)).unwrap();
+    assert_eq!(spec, expected);
+
+    let input = std::fs::read_to_string(PETSTORE).unwrap();
+    let spec = block_on(from_yaml_str_async(input)).unwrap();
+    assert_eq!(spec, expected);
+
+    let err = block_on(read_from_file_async("tests/data/missing.yaml")).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn detect_format() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("async_openapi");
+    std::fs::copy(PETSTORE, &path).unwrap();
+    let expected = read_from_file(PETSTORE).unwrap();
+    let spec = block_on(read_from_file_async(&path)).unwrap();
+    assert_eq!(spec, expected);
+}
@@ -0,0 +1,23 @@
+use std::collections::HashMap;
+
+use openapi::{select_media_type, MediaType};
+
+#[test]
+fn select() {
+    let content: HashMap<String, MediaType> = ["application/json", "text/*", "*/*"]
+        .into_iter()
+        .map(|key| (key.to_owned(), MediaType::default()))
+        .collect();
+    let select = |accept| select_media_type(&content, accept).map(|(key, _)| key);
+    assert_eq!(select("application/json"), Some("application/json"));
+    assert_eq!(
+        select("Application/JSON; charset=utf-8"),
+        Some("application/json")
+    );
+    assert_eq!(select("text/plain"), Some("text/*"));
+    assert_eq!(select("image/png"), Some("*/*"));
+    assert_eq!(select("invalid"), None);
+
+    let content = HashMap::from([("text/plain".to_owned(), MediaType::default())]);
+    assert!(select_media_type(&content, "text/html").is_none());
+}
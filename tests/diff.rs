@@ -0,0 +1,286 @@
+#![cfg(feature = "json")]
+
+use openapi::{ChangeKind, Spec};
+use serde_json::json;
+
+fn spec() -> serde_json::Value {
+    json!({
+        "openapi": "3.1.0",
+        "info": { "title": "Pets", "version": "1.0.0" },
+        "paths": {
+            "/pets": {
+                "parameters": [
+                    { "name": "tenant", "in": "header", "schema": { "type": "string" } },
+                ],
+                "get": {
+                    "parameters": [
+                        { "name": "limit", "in": "query", "schema": { "type": "integer" } },
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "List of pets",
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "type": "array",
+                                        "items": { "$ref": "#/components/schemas/Pet" },
+                                    },
+                                },
+                            },
+                        },
+                        "default": { "description": "Error" },
+                    },
+                },
+                "post": {
+                    "requestBody": {
+                        "content": {
+                            "application/json": {
+                                "schema": { "$ref": "#/components/schemas/Pet" },
+                            },
+                        },
+                    },
+                    "responses": { "201": { "description": "Created" } },
+                },
+            },
+            "/pets/{id}": {
+                "delete": {
+                    "parameters": [
+                        { "name": "id", "in": "path", "required": true },
+                    ],
+                    "responses": { "204": { "description": "Deleted" } },
+                },
+            },
+        },
+        "components": {
+            "schemas": {
+                "Pet": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "tag": { "type": "string" },
+                        "kind": { "enum": ["cat", "dog", "fish"] },
+                        "parent": { "$ref": "#/components/schemas/Pet" },
+                    },
+                },
+            },
+        },
+    })
+}
+
+fn parse(value: serde_json::Value) -> Spec {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn no_changes() {
+    let spec = parse(spec());
+    let diff = spec.diff(&spec);
+    assert!(diff.is_empty());
+    assert!(!diff.is_breaking());
+}
+
+#[test]
+fn paths_and_operations() {
+    let old = parse(spec());
+    let mut new = spec();
+    let paths = new["paths"].as_object_mut().unwrap();
+    paths.remove("/pets/{id}");
+    paths.insert("/owners".to_owned(), json!({ "get": {} }));
+    let diff = old.diff(&parse(new));
+    let changes: Vec<_> = diff
+        .changes
+        .iter()
+        .map(|c| (&*c.location, c.kind, c.breaking))
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            ("/paths/~1pets~1{id}", ChangeKind::Removed, true),
+            ("/paths/~1owners", ChangeKind::Added, false),
+        ]
+    );
+}
+
+#[test]
+fn parameters() {
+    let old = parse(spec());
+    let mut new = spec();
+    let get = &mut new["paths"]["/pets"]["get"];
+    get["parameters"][0]["required"] = json!(true);
+    get["parameters"]
+        .as_array_mut()
+        .unwrap()
+        .push(json!({ "name": "offset", "in": "query", "required": true }));
+    new["paths"]["/pets"]["parameters"] = json!([]);
+    let diff = old.diff(&parse(new));
+    let changes: Vec<_> = diff
+        .changes
+        .iter()
+        .map(|c| (&*c.location, c.breaking, &*c.message))
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            (
+                "/paths/~1pets/get/parameters/0/required",
+                true,
+                "parameter `limit` became required"
+            ),
+            (
+                "/paths/~1pets/parameters/0",
+                false,
+                "parameter `tenant` removed"
+            ),
+            (
+                "/paths/~1pets/get/parameters/1",
+                true,
+                "required parameter `offset` added"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn responses() {
+    let old = parse(spec());
+    let mut new = spec();
+    let responses = new["paths"]["/pets"]["get"]["responses"]
+        .as_object_mut()
+        .unwrap();
+    responses.remove("default");
+    responses.insert("404".to_owned(), json!({ "description": "Not found" }));
+    let diff = old.diff(&parse(new));
+    let changes: Vec<_> = diff
+        .changes
+        .iter()
+        .map(|c| (&*c.location, c.kind, c.breaking))
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            (
+                "/paths/~1pets/get/responses/default",
+                ChangeKind::Removed,
+                true
+            ),
+            ("/paths/~1pets/get/responses/404", ChangeKind::Added, false),
+        ]
+    );
+}
+
+#[test]
+fn schemas() {
+    let old = parse(spec());
+    let mut new = spec();
+    let pet = &mut new["components"]["schemas"]["Pet"];
+    pet["required"] = json!(["name", "kind"]);
+    pet["properties"]["kind"]["enum"] = json!(["cat", "dog"]);
+    pet["properties"]["name"]["type"] = json!(["string", "null"]);
+    pet["properties"].as_object_mut().unwrap().remove("tag");
+    let diff = old.diff(&parse(new));
+
+    let request = "/paths/~1pets/post/requestBody/content/application~1json/schema";
+    let response = "/paths/~1pets/get/responses/200/content/application~1json/schema/items";
+    let mut changes: Vec<_> = diff
+        .changes
+        .iter()
+        .map(|c| (&*c.location, c.breaking, &*c.message))
+        .collect();
+    changes.sort();
+    let mut expected = [
+        (
+            format!("{}/properties/name/type", response),
+            true,
+            "type changed from `string` to `null`/`string`",
+        ),
+        (
+            format!("{}/properties/kind/enum", response),
+            false,
+            "enum value `\"fish\"` removed",
+        ),
+        (
+            format!("{}/required", response),
+            false,
+            "property `kind` became required",
+        ),
+        (
+            format!("{}/properties/tag", response),
+            true,
+            "property `tag` removed",
+        ),
+        (
+            format!("{}/properties/name/type", request),
+            false,
+            "type changed from `string` to `null`/`string`",
+        ),
+        (
+            format!("{}/properties/kind/enum", request),
+            true,
+            "enum value `\"fish\"` removed",
+        ),
+        (
+            format!("{}/required", request),
+            true,
+            "property `kind` became required",
+        ),
+        (
+            format!("{}/properties/tag", request),
+            false,
+            "property `tag` removed",
+        ),
+    ];
+    expected.sort();
+    let expected: Vec<_> = expected.iter().map(|(l, b, m)| (&**l, *b, *m)).collect();
+    assert_eq!(changes, expected);
+}
+
+#[test]
+fn serialize() {
+    let old = parse(spec());
+    let mut new = spec();
+    new["paths"].as_object_mut().unwrap().remove("/pets/{id}");
+    let diff = old.diff(&parse(new));
+    let output = serde_json::to_value(&diff).unwrap();
+    assert_eq!(
+        output,
+        json!({
+            "changes": [{
+                "location": "/paths/~1pets~1{id}",
+                "kind": "removed",
+                "breaking": true,
+                "message": "path removed",
+            }],
+        })
+    );
+}
+
+#[test]
+fn breaking_changes_since() {
+    let old = parse(spec());
+    let mut new = spec();
+    new["paths"]["/pets"]["get"]["responses"]
+        .as_object_mut()
+        .unwrap()
+        .remove("default");
+    new["paths"]["/pets"]["get"]["parameters"][0]["required"] = json!(true);
+    new["paths"]["/pets/{id}"]["delete"]["parameters"][0]["schema"] = json!({ "type": "integer" });
+    let pet = &mut new["components"]["schemas"]["Pet"];
+    pet["properties"]["kind"]["enum"] = json!(["cat", "dog"]);
+    pet["properties"]["name"]["type"] = json!("integer");
+    pet["properties"]["tag"]["description"] = json!("Free form tag");
+
+    let changes = parse(new).breaking_changes_since(&old);
+    let changes: Vec<String> = changes.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        changes,
+        [
+            "`/paths/~1pets/get/parameters/0/required`: parameter `limit` became required",
+            "`/paths/~1pets/get/responses/200/content/application~1json/schema/items/properties/name/type`: type changed from `string` to `integer`",
+            "`/paths/~1pets/get/responses/default`: response `default` removed",
+            "`/paths/~1pets/post/requestBody/content/application~1json/schema/properties/kind/enum`: enum value `\"fish\"` removed",
+            "`/paths/~1pets/post/requestBody/content/application~1json/schema/properties/name/type`: type changed from `string` to `integer`",
+        ]
+    );
+    assert!(old.breaking_changes_since(&old).is_empty());
+}
@@ -0,0 +1,54 @@
+use openapi::{Format, FormatOrString, HttpMethod, Type, Version};
+
+#[test]
+fn http_method() {
+    for method in HttpMethod::ALL {
+        assert_eq!(method.to_string().parse::<HttpMethod>().unwrap(), method);
+    }
+    assert_eq!("GET".parse::<HttpMethod>().unwrap(), HttpMethod::Get);
+    let err = "CONNECT".parse::<HttpMethod>().unwrap_err();
+    assert_eq!(err.to_string(), "unknown HTTP method `CONNECT`");
+}
+
+#[test]
+fn r#type() {
+    assert_eq!(Type::String.to_string(), "string");
+    assert_eq!("integer".parse::<Type>().unwrap(), Type::Integer);
+    assert!("int".parse::<Type>().is_err());
+}
+
+#[test]
+fn format() {
+    assert_eq!(Format::DateTime.to_string(), "date-time");
+    assert_eq!("date-time".parse::<Format>().unwrap(), Format::DateTime);
+    assert_eq!("url".parse::<Format>().unwrap(), Format::Uri);
+    let err = "money".parse::<Format>().unwrap_err();
+    assert_eq!(err.to_string(), "unknown format `money`");
+
+    let format: FormatOrString = "money".parse().unwrap();
+    assert_eq!(format, FormatOrString::Other("money".to_owned()));
+    assert_eq!(format.to_string(), "money");
+    let format: FormatOrString = "int64".parse().unwrap();
+    assert_eq!(format, FormatOrString::Format(Format::Int64));
+    assert_eq!(format.to_string(), "int64");
+}
+
+#[test]
+fn version() {
+    assert_eq!(Version::OpenApi3_1(0).to_string(), "3.1.0");
+    assert_eq!(Version::OpenApi3_1(1).as_str(), "3.1.1");
+    assert_eq!(Version::OpenApi3_0.as_str(), "3.0.0");
+    assert_eq!("3.0.3".parse::<Version>().unwrap(), Version::OpenApi3_0_3);
+    assert!("3.1".parse::<Version>().is_err());
+    assert_eq!("3.1.0".parse::<Version>().unwrap(), Version::OpenApi3_1(0));
+    assert_eq!(Version::try_from("3.1.1").unwrap(), Version::OpenApi3_1(1));
+    assert!("3.1.".parse::<Version>().is_err());
+    assert!("3.1.+1".parse::<Version>().is_err());
+    assert!("3.1.x".parse::<Version>().is_err());
+    assert!("3.0.4".parse::<Version>().is_err());
+    let err = "2.0".parse::<Version>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unsupported OpenAPI version `2.0`, supported versions are 3.0.0, 3.0.1, 3.0.2, 3.0.3 and 3.1.x"
+    );
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "json")]
+
+use openapi::{Example, MediaType, Spec, Value};
+use serde_json::json;
+
+#[test]
+fn example_only_value() {
+    let example: Example = serde_json::from_value(json!({ "value": { "id": 1 } })).unwrap();
+    assert_eq!(example.value, Some(json!({ "id": 1 }).into()));
+    assert_eq!(example.external_value, None);
+}
+
+#[test]
+fn resolved_examples() {
+    let spec: Spec = serde_json::from_value(json!({
+        "openapi": "3.1.0",
+        "info": { "title": "Examples", "version": "1.0.0" },
+        "components": {
+            "examples": {
+                "Cat": { "value": { "name": "Tom" } },
+            },
+        },
+    }))
+    .unwrap();
+    let media_type: MediaType = serde_json::from_value(json!({
+        "examples": {
+            "inline": { "value": { "name": "Rex" } },
+            "referenced": { "$ref": "#/components/examples/Cat" },
+            "external": { "externalValue": "https://example.com/pet.json" },
+            "missing": { "$ref": "#/components/examples/Missing" },
+        },
+    }))
+    .unwrap();
+
+    let examples = media_type.resolved_examples(&spec);
+    assert_eq!(examples.len(), 2);
+    assert_eq!(examples["inline"], &Value::from(json!({ "name": "Rex" })));
+    assert_eq!(
+        examples["referenced"],
+        &Value::from(json!({ "name": "Tom" }))
+    );
+
+    let examples = media_type.external_examples(&spec);
+    assert_eq!(examples.len(), 1);
+    assert_eq!(examples["external"], "https://example.com/pet.json");
+}
@@ -0,0 +1,43 @@
+use openapi::{ExpressionSource, RuntimeExpression};
+
+#[test]
+fn round_trip() {
+    let tests = [
+        "$url",
+        "$method",
+        "$statusCode",
+        "$request.header.X-Request-Id",
+        "$request.query.queryUrl",
+        "$request.path.id",
+        "$request.body",
+        "$response.body#/pets/0/name~1id",
+    ];
+    for input in tests {
+        let expression: RuntimeExpression = input.parse().unwrap();
+        assert_eq!(expression.to_string(), input);
+    }
+
+    let expression: RuntimeExpression = "$response.body#/id".parse().unwrap();
+    assert!(matches!(
+        expression,
+        RuntimeExpression::Response(ExpressionSource::Body(Some(pointer))) if pointer == "/id"
+    ));
+}
+
+#[test]
+fn invalid() {
+    let tests = [
+        "",
+        "url",
+        "$uri",
+        "$request",
+        "$request.cookie.id",
+        "$request.header.",
+        "$request.header.X Id",
+        "$response.body#id",
+        "$response.body#/~2",
+    ];
+    for input in tests {
+        assert!(input.parse::<RuntimeExpression>().is_err(), "{}", input);
+    }
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "json")]
+
+use openapi::Operation;
+use serde_json::json;
+
+#[test]
+fn operation_extensions() {
+    let input = json!({
+        "operationId": "listPets",
+        "x-custom": { "internal": true },
+    });
+    let operation: Operation = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(
+        operation.extensions["x-custom"],
+        json!({ "internal": true }).into()
+    );
+    let output = serde_json::to_value(&operation).unwrap();
+    assert_eq!(output["x-custom"], input["x-custom"]);
+}
+
+#[test]
+fn unknown_fields_are_ignored() {
+    let input = json!({
+        "operationId": "listPets",
+        "descriptions": "Typo",
+    });
+    let operation: Operation = serde_json::from_value(input).unwrap();
+    assert!(operation.extensions.is_empty());
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "yaml")]
+
+use openapi::Spec;
+
+const SPEC: &str = r##"
+openapi: 3.1.0
+info:
+  title: Extract
+  version: 1.0.0
+paths:
+  /owners:
+    get:
+      responses:
+        "200":
+          description: Owner.
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  name:
+                    type: string
+                  address:
+                    type: object
+                    properties:
+                      street:
+                        type: string
+  /pets:
+    get:
+      responses:
+        "200":
+          description: Pet.
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  id:
+                    type: integer
+                  owner:
+                    $ref: "#/components/schemas/Owner"
+                  address:
+                    type: object
+                    properties:
+                      street:
+                        type: string
+components:
+  schemas:
+    Owner:
+      type: object
+      properties:
+        name:
+          type: string
+        address:
+          type: object
+          properties:
+            street:
+              type: string
+"##;
+
+#[test]
+fn extract_inline_schemas() {
+    let mut spec = Spec::from_yaml_str(SPEC).unwrap();
+    spec.extract_inline_schemas();
+
+    let schema = |path: &str| {
+        let operation = spec.paths[path].get.as_ref().unwrap();
+        let response = &operation.responses.as_ref().unwrap().response["200"];
+        let content = &response.object.as_ref().unwrap().content;
+        content["application/json"].schema.clone().unwrap()
+    };
+    // Identical to an existing component.
+    assert_eq!(
+        schema("/owners").r#ref.as_deref(),
+        Some("#/components/schemas/Owner")
+    );
+    // Used multiple times.
+    let pet = schema("/pets");
+    let address = &pet.properties.as_ref().unwrap()["address"];
+    assert_eq!(
+        address.r#ref.as_deref(),
+        Some("#/components/schemas/Address")
+    );
+    let owner = &spec.components.schemas["Owner"];
+    let address = &owner.properties.as_ref().unwrap()["address"];
+    assert_eq!(
+        address.r#ref.as_deref(),
+        Some("#/components/schemas/Address")
+    );
+    // Trivial schemas are left as is.
+    assert!(pet.properties.as_ref().unwrap()["id"].r#ref.is_none());
+    assert_eq!(spec.components.schemas.len(), 2);
+    assert!(spec.check_references().is_empty());
+}
+
+#[test]
+fn extract_inline_schemas_with() {
+    let mut spec = Spec::from_yaml_str(SPEC).unwrap();
+    spec.components.schemas.clear();
+    spec.extract_inline_schemas_with(|_, _| "Extracted".to_owned());
+    let mut names: Vec<&String> = spec.components.schemas.keys().collect();
+    names.sort();
+    assert_eq!(names, ["Extracted"]);
+}
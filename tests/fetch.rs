@@ -0,0 +1,53 @@
+#![cfg(all(feature = "reqwest", feature = "yaml"))]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread;
+
+use openapi::{read_from_file, read_from_url, FetchError};
+
+const PETSTORE: &str = "tests/data/petstore.yaml";
+
+/// Start a server that responds to a single request with `status`, and
+/// returns the URL to it.
+fn serve(status: &'static str, content_type: &'static str, body: String) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 2 {
+            line.clear();
+        }
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            content_type,
+            body.len(),
+            body
+        );
+        reader.get_mut().write_all(response.as_bytes()).unwrap();
+    });
+    format!("http://{}/openapi", address)
+}
+
+#[test]
+fn fetch() {
+    let body = std::fs::read_to_string(PETSTORE).unwrap();
+    let url = serve("200 OK", "application/yaml", body.clone());
+    assert_eq!(
+        read_from_url(&url).unwrap(),
+        read_from_file(PETSTORE).unwrap()
+    );
+
+    // Unknown content type, detected from the contents.
+    let url = serve("200 OK", "text/plain", body);
+    assert_eq!(
+        read_from_url(&url).unwrap(),
+        read_from_file(PETSTORE).unwrap()
+    );
+
+    let url = serve("404 Not Found", "text/plain", String::new());
+    assert!(matches!(read_from_url(&url), Err(FetchError::Status(404))));
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "json")]
+
+use openapi::{Schema, Spec, Type};
+use serde_json::json;
+
+fn spec() -> Spec {
+    serde_json::from_value(json!({
+        "openapi": "3.1.0",
+        "info": { "title": "Flatten", "version": "1.0.0" },
+        "components": {
+            "schemas": {
+                "Base": {
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": {
+                        "id": { "type": "integer" },
+                    },
+                    "maxProperties": 10,
+                },
+                "Alias": { "$ref": "#/components/schemas/Base" },
+            },
+        },
+    }))
+    .unwrap()
+}
+
+#[test]
+fn flatten_all_of() {
+    let schema: Schema = serde_json::from_value(json!({
+        "title": "Pet",
+        "allOf": [
+            { "$ref": "#/components/schemas/Alias" },
+            {
+                "type": ["object", "null"],
+                "required": ["name"],
+                "properties": {
+                    "name": { "type": "string" },
+                },
+                "maxProperties": 5,
+                "oneOf": [{ "required": ["a"] }, { "required": ["b"] }],
+            },
+        ],
+    }))
+    .unwrap();
+    let schema = schema.flatten_all_of(&spec()).unwrap();
+    assert!(schema.all_of.is_none());
+    assert_eq!(schema.title.as_deref(), Some("Pet"));
+    assert_eq!(schema.r#type, [Type::Object]);
+    assert_eq!(schema.required, ["id", "name"]);
+    let properties: Vec<&String> = schema.properties.as_ref().unwrap().keys().collect();
+    assert_eq!(properties, ["id", "name"]);
+    assert_eq!(schema.max_properties, Some(5));
+    assert_eq!(schema.one_of.as_ref().map(Vec::len), Some(2));
+}
+
+#[test]
+fn flatten_all_of_conflict() {
+    let schema: Schema = serde_json::from_value(json!({
+        "allOf": [
+            { "$ref": "#/components/schemas/Base" },
+            { "properties": { "id": { "type": "string" } } },
+        ],
+    }))
+    .unwrap();
+    let err = schema.flatten_all_of(&spec()).unwrap_err();
+    assert_eq!(err.location, "/allOf/1/properties/id");
+
+    let schema: Schema = serde_json::from_value(json!({
+        "allOf": [{ "type": "string" }, { "type": "integer" }],
+    }))
+    .unwrap();
+    let err = schema.flatten_all_of(&spec()).unwrap_err();
+    assert_eq!(err.location, "/allOf/1/type");
+}
+
+#[test]
+fn flatten_all_of_unresolved() {
+    let schema: Schema = serde_json::from_value(json!({
+        "allOf": [
+            { "$ref": "#/components/schemas/Missing" },
+            { "type": "object" },
+        ],
+    }))
+    .unwrap();
+    let schema = schema.flatten_all_of(&spec()).unwrap();
+    assert_eq!(schema.r#type, [Type::Object]);
+    let all_of = schema.all_of.unwrap();
+    assert_eq!(all_of.len(), 1);
+    assert_eq!(
+        all_of[0].r#ref.as_deref(),
+        Some("#/components/schemas/Missing")
+    );
+}
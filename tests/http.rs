@@ -0,0 +1,39 @@
+#![cfg(feature = "http")]
+
+use openapi::{HttpMethod, Reference, Response, Responses};
+
+#[test]
+fn method() {
+    assert_eq!(http::Method::from(HttpMethod::Get), http::Method::GET);
+    assert_eq!(http::Method::from(HttpMethod::Trace), http::Method::TRACE);
+}
+
+#[test]
+fn responses_get() {
+    let response = |description: &str| Reference {
+        r#ref: None,
+        summary: None,
+        description: None,
+        object: Some(Response {
+            description: description.to_owned(),
+            ..Response::default()
+        }),
+    };
+    let responses = Responses {
+        default: Some(response("default")),
+        response: [
+            ("200".to_owned(), response("ok")),
+            ("4XX".to_owned(), response("client error")),
+        ]
+        .into_iter()
+        .collect(),
+        ..Responses::default()
+    };
+    let description = |status| {
+        let response = responses.get(status).unwrap().object.as_ref().unwrap();
+        response.description.as_str()
+    };
+    assert_eq!(description(http::StatusCode::OK), "ok");
+    assert_eq!(description(http::StatusCode::NOT_FOUND), "client error");
+    assert_eq!(description(http::StatusCode::BAD_GATEWAY), "default");
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "json")]
+
+use openapi::{ExpressionOrValue, ExpressionSource, Link, RuntimeExpression, Value};
+use serde_json::json;
+
+#[test]
+fn parameters_expression_or_value() {
+    let input = json!({
+        "operationId": "getPet",
+        "parameters": {
+            "petId": 5,
+            "ownerId": "$response.body#/id",
+        },
+        "requestBody": "$request.body",
+    });
+    let link: Link = serde_json::from_value(input.clone()).unwrap();
+    assert!(matches!(
+        &link.parameters["petId"],
+        ExpressionOrValue::Value(Value::Number(n)) if n.as_u64() == Some(5)
+    ));
+    assert!(matches!(
+        &link.parameters["ownerId"],
+        ExpressionOrValue::Expression(RuntimeExpression::Response(ExpressionSource::Body(Some(pointer))))
+            if pointer == "/id"
+    ));
+    assert!(matches!(
+        link.request_body,
+        Some(ExpressionOrValue::Expression(RuntimeExpression::Request(
+            ExpressionSource::Body(None)
+        )))
+    ));
+    assert_eq!(serde_json::to_value(&link).unwrap(), input);
+}
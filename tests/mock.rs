@@ -0,0 +1,120 @@
+#![cfg(feature = "json")]
+
+use openapi::{Schema, Spec};
+use serde_json::json;
+
+fn spec() -> Spec {
+    serde_json::from_value(json!({
+        "openapi": "3.1.0",
+        "info": { "title": "Mock", "version": "1.0.0" },
+        "components": {
+            "schemas": {
+                "Base": {
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": {
+                        "id": { "type": "string", "format": "uuid" },
+                    },
+                },
+                "Node": {
+                    "type": "object",
+                    "required": ["next"],
+                    "properties": {
+                        "next": { "$ref": "#/components/schemas/Node" },
+                    },
+                },
+            },
+        },
+    }))
+    .unwrap()
+}
+
+fn example_value(schema: serde_json::Value) -> serde_json::Value {
+    let schema: Schema = serde_json::from_value(schema).unwrap();
+    schema.example_value(&spec()).into()
+}
+
+#[test]
+fn example_value_by_type() {
+    let tests = [
+        (json!({ "type": "string" }), json!("")),
+        (json!({ "type": "string", "minLength": 3 }), json!("aaa")),
+        (
+            json!({ "type": "string", "format": "date" }),
+            json!("1970-01-01"),
+        ),
+        (json!({ "type": "integer" }), json!(0)),
+        (json!({ "type": "integer", "minimum": 1.5 }), json!(2)),
+        (json!({ "type": "integer", "maximum": -3 }), json!(-3)),
+        (
+            json!({ "type": "number", "exclusiveMinimum": 1 }),
+            json!(2.0),
+        ),
+        (json!({ "type": "boolean" }), json!(false)),
+        (json!({ "type": ["null", "boolean"] }), json!(false)),
+        (
+            json!({ "type": "array", "minItems": 2, "items": { "type": "integer" } }),
+            json!([0, 0]),
+        ),
+        (
+            json!({
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": { "type": "string" },
+                    "tag": { "type": "string" },
+                },
+            }),
+            json!({ "name": "" }),
+        ),
+    ];
+    for (schema, expected) in tests {
+        assert_eq!(example_value(schema.clone()), expected, "{}", schema);
+    }
+}
+
+#[test]
+fn example_value_from_schema() {
+    let tests = [
+        (json!({ "type": "string", "const": "a" }), json!("a")),
+        (json!({ "type": "string", "enum": ["b", "c"] }), json!("b")),
+        (
+            json!({ "type": "integer", "default": 1, "examples": [2] }),
+            json!(2),
+        ),
+        (json!({ "type": "integer", "example": 3 }), json!(3)),
+    ];
+    for (schema, expected) in tests {
+        assert_eq!(example_value(schema.clone()), expected, "{}", schema);
+    }
+}
+
+#[test]
+fn example_value_composition() {
+    let schema = json!({
+        "allOf": [
+            { "$ref": "#/components/schemas/Base" },
+            {
+                "type": "object",
+                "required": ["count"],
+                "properties": { "count": { "type": "integer" } },
+            },
+        ],
+    });
+    let expected = json!({ "id": "00000000-0000-0000-0000-000000000000", "count": 0 });
+    assert_eq!(example_value(schema), expected);
+
+    let schema = json!({ "oneOf": [{ "type": "boolean" }, { "type": "string" }] });
+    assert_eq!(example_value(schema), json!(false));
+
+    // Recursive schemas are cut off.
+    let value = example_value(json!({ "$ref": "#/components/schemas/Node" }));
+    let mut value = &value;
+    let mut depth = 0;
+    while let Some(next) = value.get("next") {
+        value = next;
+        depth += 1;
+    }
+    assert_eq!(value, &serde_json::Value::Null);
+    assert!(depth > 1);
+}
@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+
+use openapi::{expand_path, ParameterStyle, PathError};
+
+#[test]
+fn expand_path_styles() {
+    let params = HashMap::from([
+        ("petId".to_owned(), "123".to_owned()),
+        ("name".to_owned(), "a b/c".to_owned()),
+    ]);
+    let path = expand_path("/pets/{petId}/{name}", &params, &HashMap::new()).unwrap();
+    assert_eq!(path, "/pets/123/a%20b%2Fc");
+
+    let styles = HashMap::from([
+        ("petId".to_owned(), ParameterStyle::Label),
+        ("name".to_owned(), ParameterStyle::Matrix),
+    ]);
+    let path = expand_path("/pets/{petId}/{name}", &params, &styles).unwrap();
+    assert_eq!(path, "/pets/.123/;name=a%20b%2Fc");
+}
+
+#[test]
+fn expand_path_errors() {
+    let params = HashMap::from([("petId".to_owned(), "123".to_owned())]);
+    let styles = HashMap::new();
+    assert!(matches!(
+        expand_path("/pets/{petId}/{ownerId}", &params, &styles),
+        Err(PathError::MissingParameter(name)) if name == "ownerId"
+    ));
+    assert!(matches!(
+        expand_path("/pets/{petId", &params, &styles),
+        Err(PathError::InvalidTemplate)
+    ));
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "yaml")]
+
+use openapi::{Parameter, Reference, Response, Schema, Spec};
+
+const INPUT: &str = r##"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+components:
+  schemas:
+    Pet:
+      type: object
+  responses:
+    Error:
+      description: Error
+    Alias:
+      $ref: '#/components/responses/Error'
+    Cycle:
+      $ref: '#/components/responses/Cycle'
+"##;
+
+fn reference<T>(r#ref: &str) -> Reference<T> {
+    Reference {
+        r#ref: Some(r#ref.to_owned()),
+        summary: None,
+        description: None,
+        object: None,
+    }
+}
+
+#[test]
+fn resolve_ref() {
+    let spec = Spec::from_yaml_str(INPUT).unwrap();
+    let pet = spec
+        .resolve_ref::<Schema>("#/components/schemas/Pet")
+        .unwrap();
+    assert_eq!(pet, &spec.components.schemas["Pet"]);
+    let error = spec
+        .resolve_ref::<Response>("#/components/responses/Error")
+        .unwrap();
+    assert_eq!(error.description, "Error");
+    // References between components are followed.
+    let alias = spec.resolve_ref::<Response>("#/components/responses/Alias");
+    assert_eq!(alias, Some(error));
+
+    // Missing components.
+    assert!(spec
+        .resolve_ref::<Schema>("#/components/schemas/Owner")
+        .is_none());
+    // Wrong type of component.
+    assert!(spec
+        .resolve_ref::<Parameter>("#/components/schemas/Pet")
+        .is_none());
+    // External references.
+    assert!(spec.resolve_ref::<Schema>("pet.yaml").is_none());
+    assert!(spec
+        .resolve_ref::<Schema>("pet.yaml#/components/schemas/Pet")
+        .is_none());
+    // Reference cycles.
+    assert!(spec
+        .resolve_ref::<Response>("#/components/responses/Cycle")
+        .is_none());
+}
+
+#[test]
+fn reference_resolve() {
+    let spec = Spec::from_yaml_str(INPUT).unwrap();
+    let inline = Reference {
+        r#ref: None,
+        summary: None,
+        description: None,
+        object: Some(Response {
+            description: "Inline".to_owned(),
+            ..Response::default()
+        }),
+    };
+    assert_eq!(inline.resolve(&spec).unwrap().description, "Inline");
+    let error = reference::<Response>("#/components/responses/Alias");
+    assert_eq!(error.resolve(&spec).unwrap().description, "Error");
+    assert!(reference::<Response>("#/components/responses/Missing")
+        .resolve(&spec)
+        .is_none());
+    assert!(
+        reference::<Response>("common.yaml#/components/responses/Error")
+            .resolve(&spec)
+            .is_none()
+    );
+}
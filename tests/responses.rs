@@ -0,0 +1,93 @@
+use openapi::{Header, Reference, Response, Responses, StatusCode};
+
+fn response(description: &str) -> Reference<Response> {
+    Reference {
+        r#ref: None,
+        summary: None,
+        description: None,
+        object: Some(Response {
+            description: description.to_owned(),
+            ..Response::default()
+        }),
+    }
+}
+
+#[test]
+fn for_status() {
+    let mut responses = Responses::default();
+    responses.response.insert("200".to_owned(), response("ok"));
+    responses
+        .response
+        .insert("4XX".to_owned(), response("client"));
+    responses
+        .response
+        .insert("404".to_owned(), response("not found"));
+
+    let description = |code| {
+        responses
+            .for_status(code)
+            .and_then(|r| r.object.as_ref())
+            .map(|r| &*r.description)
+    };
+    assert_eq!(description(200), Some("ok"));
+    assert_eq!(description(404), Some("not found"));
+    assert_eq!(description(400), Some("client"));
+    assert_eq!(description(500), None);
+
+    responses.default = Some(response("default"));
+    assert_eq!(
+        responses
+            .for_status(500)
+            .unwrap()
+            .object
+            .as_ref()
+            .unwrap()
+            .description,
+        "default"
+    );
+}
+
+#[test]
+fn iter_all() {
+    let mut responses = Responses {
+        default: Some(response("default")),
+        ..Responses::default()
+    };
+    responses.response.insert("200".to_owned(), response("ok"));
+    responses
+        .response
+        .insert("4XX".to_owned(), response("client"));
+
+    let keys: Vec<&str> = responses.iter_all().map(|(key, _)| key).collect();
+    assert_eq!(keys, ["200", "4XX", "default"]);
+    assert_eq!(Responses::default().iter_all().count(), 0);
+}
+
+#[test]
+fn status_code() {
+    assert_eq!(StatusCode::parse("200"), Some(StatusCode::Code(200)));
+    assert_eq!(StatusCode::parse("5XX"), Some(StatusCode::Range(5)));
+    assert_eq!(StatusCode::parse("default"), None);
+    assert_eq!(StatusCode::parse("6XX"), None);
+    assert_eq!(StatusCode::parse("2xx"), None);
+    assert_eq!(StatusCode::Range(4).to_string(), "4XX");
+    assert!(StatusCode::Range(4).matches(418));
+    assert!(!StatusCode::Code(200).matches(201));
+}
+
+#[test]
+fn header() {
+    let mut response = Response::default();
+    let header = Reference {
+        r#ref: None,
+        summary: None,
+        description: None,
+        object: Some(Header::default()),
+    };
+    response.headers.insert("ETag".to_owned(), header);
+
+    assert!(response.header("ETag").is_some());
+    assert!(response.header("etag").is_some());
+    assert!(response.header("Etag").is_some());
+    assert!(response.header("Last-Modified").is_none());
+}
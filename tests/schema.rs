@@ -0,0 +1,422 @@
+#![cfg(feature = "json")]
+
+use openapi::{BoolOr, Format, FormatOrString, ParseError, ParseOptions, Schema, Spec, Type};
+use serde_json::json;
+
+#[test]
+fn enum_mixed_types() {
+    let input = json!({ "enum": [1, "two", 3.5, true, null, { "a": [] }] });
+    let schema: Schema = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(schema.r#enum.len(), 6);
+    let output = serde_json::to_value(&schema).unwrap();
+    assert_eq!(output["enum"], input["enum"]);
+}
+
+#[test]
+fn dynamic_references() {
+    let input = json!({
+        "$anchor": "tree",
+        "$dynamicAnchor": "node",
+        "type": "object",
+        "properties": {
+            "children": { "type": "array", "items": { "$dynamicRef": "#node" } },
+        },
+    });
+    let schema: Schema = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(schema.anchor.as_deref(), Some("tree"));
+    assert_eq!(schema.dynamic_anchor.as_deref(), Some("node"));
+    let items = schema.properties.as_ref().unwrap()["children"]
+        .items
+        .as_ref();
+    let items = items.unwrap();
+    assert_eq!(items.dynamic_ref.as_deref(), Some("#node"));
+    let output = serde_json::to_value(&schema).unwrap();
+    assert_eq!(output["$anchor"], input["$anchor"]);
+    assert_eq!(output["$dynamicAnchor"], input["$dynamicAnchor"]);
+    assert_eq!(serde_json::from_value::<Schema>(output).unwrap(), schema);
+}
+
+#[test]
+fn defs() {
+    let input = json!({
+        "$defs": { "Name": { "type": "string" } },
+        "properties": { "name": { "$ref": "#/components/schemas/Pet/$defs/Name" } },
+    });
+    let schema: Schema = serde_json::from_value(input).unwrap();
+    assert_eq!(schema.defs["Name"].r#type, [Type::String]);
+    let output = serde_json::to_value(&schema).unwrap();
+    assert_eq!(output["$defs"]["Name"]["type"], "string");
+    assert_eq!(serde_json::from_value::<Schema>(output).unwrap(), schema);
+
+    let legacy: Schema =
+        serde_json::from_value(json!({ "definitions": { "Name": { "type": "string" } } })).unwrap();
+    assert_eq!(legacy.defs, schema.defs);
+}
+
+#[test]
+fn unique_items() {
+    let schema: Schema = serde_json::from_value(json!({ "uniqueItems": true })).unwrap();
+    assert!(schema.unique_items);
+}
+
+#[test]
+fn boolean_schemas() {
+    let schema: Schema = serde_json::from_value(json!(false)).unwrap();
+    assert!(schema.validate_instance(&json!(123).into()).is_err());
+
+    let schema: Schema = serde_json::from_value(json!({ "items": true })).unwrap();
+    let items = schema.items.as_ref().unwrap();
+    assert!(items.validate_instance(&json!(123).into()).is_ok());
+    assert!(schema.validate_instance(&json!([1, "two"]).into()).is_ok());
+}
+
+#[test]
+fn unsupported_dialect() {
+    let input =
+        json!({ "$schema": "https://json-schema.org/draft/2020-12/schema", "type": "string" });
+    let schema: Schema = serde_json::from_value(input).unwrap();
+    assert!(schema.validate_instance(&json!("a").into()).is_ok());
+
+    let input = json!({ "$schema": "http://json-schema.org/draft-04/schema#", "type": "string" });
+    let schema: Schema = serde_json::from_value(input).unwrap();
+    let errors = schema.validate_instance(&json!("a").into()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].keyword, "$schema");
+}
+
+#[test]
+fn numbers_round_trip() {
+    let input = r#"{"const":1,"default":-2,"enum":[1,1.0,2.5],"example":10000000000000000000}"#;
+    let schema: Schema = serde_json::from_str(input).unwrap();
+    let output = serde_json::to_value(&schema).unwrap();
+    assert_eq!(output["const"].to_string(), "1");
+    assert_eq!(output["default"].to_string(), "-2");
+    assert_eq!(output["enum"].to_string(), "[1,1.0,2.5]");
+    assert_eq!(output["example"].to_string(), "10000000000000000000");
+}
+
+#[test]
+#[cfg(feature = "yaml")]
+fn numbers_round_trip_yaml() {
+    let schema: Schema = serde_yaml::from_str("const: 1\nenum: [1, 1.0]").unwrap();
+    let output = serde_yaml::to_string(&schema).unwrap();
+    assert!(output.contains("const: 1\n"), "{}", output);
+    assert!(output.contains("- 1\n"), "{}", output);
+    assert!(output.contains("- 1.0\n"), "{}", output);
+}
+
+#[test]
+fn content_schema() {
+    let input = json!({
+        "type": "string",
+        "contentMediaType": "application/json",
+        "contentSchema": { "type": "array", "items": { "type": "integer" } },
+    });
+    let mut schema: Schema = serde_json::from_value(input).unwrap();
+    let content_schema = schema.content_schema().unwrap();
+    assert_eq!(content_schema.r#type, [Type::Array]);
+
+    schema.content_media_type = None;
+    assert!(schema.content_schema().is_none());
+}
+
+#[test]
+fn numeric_keywords() {
+    let schema: Schema = serde_json::from_value(json!({ "multipleOf": 0.1 })).unwrap();
+    assert!(schema.validate_instance(&json!(0.3).into()).is_ok());
+    assert!(schema.validate_instance(&json!(7).into()).is_ok());
+    let errors = schema.validate_instance(&json!(0.15).into()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].keyword, "multipleOf");
+    assert_eq!(errors[0].message, "0.15 is not a multiple of 0.1");
+
+    let input = json!({ "exclusiveMinimum": 0, "exclusiveMaximum": 10 });
+    let schema: Schema = serde_json::from_value(input).unwrap();
+    assert!(schema.validate_instance(&json!(5).into()).is_ok());
+    let errors = schema.validate_instance(&json!(10).into()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].keyword, "exclusiveMaximum");
+    assert_eq!(errors[0].message, "10 is not less than 10");
+    let errors = schema.validate_instance(&json!(0).into()).unwrap_err();
+    assert_eq!(errors[0].keyword, "exclusiveMinimum");
+
+    let schema: Schema = serde_json::from_value(json!({ "minimum": 0, "maximum": 10 })).unwrap();
+    assert!(schema.validate_instance(&json!(10).into()).is_ok());
+    let errors = schema.validate_instance(&json!(-1).into()).unwrap_err();
+    assert_eq!(errors[0].keyword, "minimum");
+}
+
+#[test]
+fn additional_properties() {
+    let input = json!({
+        "properties": { "a": { "type": "integer" } },
+        "additionalProperties": false,
+    });
+    let schema: Schema = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(schema.additional_properties, Some(BoolOr::Bool(false)));
+    assert!(schema.validate_instance(&json!({ "a": 1 }).into()).is_ok());
+    assert!(schema.validate_instance(&json!({ "b": 1 }).into()).is_err());
+    let output = serde_json::to_value(&schema).unwrap();
+    assert_eq!(
+        output["additionalProperties"],
+        input["additionalProperties"]
+    );
+
+    let input = json!({ "additionalProperties": { "type": "string" } });
+    let schema: Schema = serde_json::from_value(input).unwrap();
+    assert!(matches!(
+        schema.additional_properties,
+        Some(BoolOr::Item(_))
+    ));
+    assert!(schema
+        .validate_instance(&json!({ "b": "1" }).into())
+        .is_ok());
+    assert!(schema.validate_instance(&json!({ "b": 1 }).into()).is_err());
+}
+
+#[test]
+fn type_round_trip() {
+    let tests = [
+        (json!({}), vec![]),
+        (json!({ "type": "string" }), vec![Type::String]),
+        (
+            json!({ "type": ["string", "null"] }),
+            vec![Type::String, Type::Null],
+        ),
+        (
+            json!({ "type": ["integer", "number", "boolean"] }),
+            vec![Type::Integer, Type::Number, Type::Boolean],
+        ),
+    ];
+    for (input, expected) in tests {
+        let schema: Schema = serde_json::from_value(input.clone()).unwrap();
+        assert_eq!(schema.r#type, expected);
+        let output = serde_json::to_value(&schema).unwrap();
+        assert_eq!(output.get("type"), input.get("type"));
+    }
+
+    // An empty array is the same as no type constraint.
+    let schema: Schema = serde_json::from_value(json!({ "type": [] })).unwrap();
+    assert!(schema.r#type.is_empty());
+    let output = serde_json::to_value(&schema).unwrap();
+    assert_eq!(output.get("type"), None);
+}
+
+#[test]
+fn format() {
+    let format = |input| {
+        let schema: Schema = serde_json::from_value(json!({ "format": input })).unwrap();
+        schema.format.unwrap()
+    };
+    let int64 = format("int64");
+    assert_eq!(int64.as_known(), Some(&Format::Int64));
+    assert!(int64.is_numeric());
+    assert!(!int64.is_string_like());
+    let uuid = format("uuid");
+    assert!(!uuid.is_numeric());
+    assert!(uuid.is_string_like());
+    let other = format("my-format");
+    assert_eq!(other, FormatOrString::Other("my-format".to_owned()));
+    assert_eq!(other.as_known(), None);
+    assert!(!other.is_numeric());
+    assert!(!other.is_string_like());
+
+    assert_eq!(Format::Float.rust_type(), "f32");
+    assert_eq!(Format::Binary.rust_type(), "Vec<u8>");
+    assert_eq!(Format::DateTime.rust_type(), "String");
+}
+
+#[test]
+fn nullable() {
+    let schema: Schema =
+        serde_json::from_value(json!({ "type": "string", "nullable": true })).unwrap();
+    assert_eq!(schema.r#type, [Type::String, Type::Null]);
+    assert!(!schema.nullable);
+    let output = serde_json::to_value(&schema).unwrap();
+    assert!(output.get("nullable").is_none());
+
+    let mut schema = Schema {
+        r#type: vec![Type::Integer],
+        nullable: true,
+        ..Schema::default()
+    };
+    assert_eq!(serde_json::to_value(&schema).unwrap()["nullable"], true);
+    schema.normalize_nullable();
+    assert_eq!(schema.r#type, [Type::Integer, Type::Null]);
+    assert!(!schema.nullable);
+}
+
+#[test]
+fn openapi_3_0_keywords() {
+    let tests = [
+        (
+            json!({ "type": "string", "nullable": true }),
+            json!({ "type": ["string", "null"] }),
+        ),
+        (
+            json!({ "type": ["string", "null"], "nullable": true }),
+            json!({ "type": ["string", "null"] }),
+        ),
+        (
+            json!({ "type": "string", "nullable": false }),
+            json!({ "type": "string" }),
+        ),
+        (
+            json!({ "maximum": 10, "exclusiveMaximum": true }),
+            json!({ "exclusiveMaximum": 10.0 }),
+        ),
+        (
+            json!({ "maximum": 10, "exclusiveMaximum": false }),
+            json!({ "maximum": 10.0 }),
+        ),
+        (
+            json!({ "minimum": 1, "exclusiveMinimum": true }),
+            json!({ "exclusiveMinimum": 1.0 }),
+        ),
+        (
+            json!({ "minimum": 1, "exclusiveMinimum": false }),
+            json!({ "minimum": 1.0 }),
+        ),
+        // OpenAPI v3.1 keywords are kept as is.
+        (
+            json!({ "exclusiveMinimum": 1, "exclusiveMaximum": 10 }),
+            json!({ "exclusiveMinimum": 1.0, "exclusiveMaximum": 10.0 }),
+        ),
+    ];
+    for (input, expected) in tests {
+        let schema: Schema = serde_json::from_value(input.clone()).unwrap();
+        let expected: Schema = serde_json::from_value(expected).unwrap();
+        assert_eq!(schema, expected, "input: {}", input);
+        let output = serde_json::to_value(&schema).unwrap();
+        assert!(output.get("nullable").is_none(), "output: {}", output);
+    }
+}
+
+#[test]
+fn helpers() {
+    let schema: Schema = serde_json::from_value(json!({
+        "type": ["string", "null"],
+        "properties": { "id": {}, "name": {} },
+        "required": ["id"],
+    }))
+    .unwrap();
+    assert!(schema.is_nullable());
+    assert_eq!(schema.primary_type(), Some(Type::String));
+    assert!(schema.is_required("id"));
+    assert!(!schema.is_required("name"));
+
+    let schema = Schema::default();
+    assert!(!schema.is_nullable());
+    assert_eq!(schema.primary_type(), None);
+
+    let schema = Schema {
+        r#type: vec![Type::String, Type::Integer],
+        ..Schema::default()
+    };
+    assert!(!schema.is_nullable());
+    assert_eq!(schema.primary_type(), None);
+}
+
+#[test]
+fn max_depth() {
+    let mut schema = json!({ "type": "string" });
+    for _ in 0..1000 {
+        schema = json!({ "type": "array", "items": schema });
+    }
+    let spec = json!({
+        "openapi": "3.1.0",
+        "info": { "title": "Test", "version": "1.0.0" },
+        "components": { "schemas": { "Deep": schema } },
+    });
+    let input = serde_json::to_string(&spec).unwrap();
+    match Spec::from_json_str(&input) {
+        Err(ParseError::TooDeep) => {}
+        res => panic!("unexpected result: {:?}", res.map(|_| ())),
+    }
+
+    let mut schema = json!({ "type": "string" });
+    for _ in 0..30 {
+        schema = json!({ "type": "array", "items": schema });
+    }
+    let spec = json!({
+        "openapi": "3.1.0",
+        "info": { "title": "Test", "version": "1.0.0" },
+        "components": { "schemas": { "Deep": schema } },
+    });
+    let input = serde_json::to_string(&spec).unwrap();
+    Spec::from_json_str(&input).unwrap();
+    ParseOptions::default().from_json_str(&input).unwrap();
+
+    let options = ParseOptions::new().max_depth(3);
+    let input = r#"{"openapi": "3.1.0", "info": {"title": "Test", "version": "1.0.0"}}"#;
+    options.from_json_str(input).unwrap();
+    let options = ParseOptions::new().max_depth(2);
+    assert!(matches!(
+        options.from_json_str(input),
+        Err(ParseError::TooDeep)
+    ));
+}
+
+#[test]
+#[cfg(feature = "regex")]
+fn pattern_properties() {
+    let input = json!({
+        "properties": { "a_id": { "minimum": 1 } },
+        "patternProperties": {
+            "^a_": { "type": "integer" },
+            "_id$": { "maximum": 10 },
+        },
+        "additionalProperties": false,
+    });
+    let schema: Schema = serde_json::from_value(input).unwrap();
+    assert!(schema
+        .validate_instance(&json!({ "a_b": 1, "a_id": 5, "b_id": 1.5 }).into())
+        .is_ok());
+    // Validated against all matching schemas.
+    let errors = schema
+        .validate_instance(&json!({ "a_id": 20.5, "b": 1 }).into())
+        .unwrap_err();
+    let errors: Vec<_> = errors
+        .iter()
+        .map(|e| (&*e.instance_path, e.keyword))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            ("/a_id", "type"),
+            ("/a_id", "maximum"),
+            ("", "additionalProperties"),
+        ]
+    );
+
+    let input = json!({
+        "patternProperties": { "a(": { "type": "integer" } },
+        "additionalProperties": false,
+    });
+    let schema: Schema = serde_json::from_value(input).unwrap();
+    let errors = schema
+        .validate_instance(&json!({ "a(": 1 }).into())
+        .unwrap_err();
+    let errors: Vec<_> = errors
+        .iter()
+        .map(|e| (&*e.instance_path, e.keyword))
+        .collect();
+    assert_eq!(
+        errors,
+        [("", "patternProperties"), ("", "additionalProperties")]
+    );
+}
+
+#[test]
+#[cfg(not(feature = "regex"))]
+fn pattern_properties_without_regex() {
+    let input = json!({
+        "patternProperties": { "^a_": { "type": "integer" } },
+        "additionalProperties": false,
+    });
+    let schema: Schema = serde_json::from_value(input).unwrap();
+    // Patterns can't be matched, so all properties are assumed to match.
+    assert!(schema
+        .validate_instance(&json!({ "a_b": "1", "b": 1 }).into())
+        .is_ok());
+}
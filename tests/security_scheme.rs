@@ -0,0 +1,72 @@
+#![cfg(feature = "json")]
+
+use openapi::{SecurityScheme, SecuritySchemeIn, SecuritySchemeKind, SecuritySchemeType};
+use serde_json::json;
+
+#[test]
+fn kind_conversions() {
+    let input = json!({ "type": "apiKey", "name": "api_key", "in": "header" });
+    let scheme: SecurityScheme = serde_json::from_value(input.clone()).unwrap();
+    let kind = SecuritySchemeKind::try_from(scheme.clone()).unwrap();
+    assert_eq!(
+        kind,
+        SecuritySchemeKind::ApiKey {
+            name: "api_key".to_owned(),
+            r#in: SecuritySchemeIn::Header,
+        }
+    );
+    assert_eq!(serde_json::to_value(&kind).unwrap(), input);
+    assert_eq!(SecurityScheme::from(kind), scheme);
+
+    let mut scheme = scheme;
+    scheme.r#type = SecuritySchemeType::Http;
+    let errors = SecuritySchemeKind::try_from(scheme).unwrap_err();
+    assert_eq!(errors[0].location, "/scheme");
+
+    let tests = [
+        ("apiKey", &["/name", "/in"][..]),
+        ("http", &["/scheme"]),
+        ("oauth2", &["/flows"]),
+        ("openIdConnect", &["/openIdConnectUrl"]),
+    ];
+    for (r#type, expected) in tests {
+        let scheme: SecurityScheme = serde_json::from_value(json!({ "type": r#type })).unwrap();
+        let errors = SecuritySchemeKind::try_from(scheme).unwrap_err();
+        let locations: Vec<&str> = errors.iter().map(|e| &*e.location).collect();
+        assert_eq!(locations, expected, "type: {}", r#type);
+    }
+}
+
+#[test]
+fn kind_serde() {
+    let tests = [
+        (
+            json!({ "type": "http", "scheme": "bearer", "bearerFormat": "JWT" }),
+            SecuritySchemeKind::Http {
+                scheme: "bearer".to_owned(),
+                bearer_format: Some("JWT".to_owned()),
+            },
+        ),
+        (
+            json!({ "type": "mutualTLS" }),
+            SecuritySchemeKind::MutualTls,
+        ),
+        (
+            json!({ "type": "openIdConnect", "openIdConnectUrl": "https://example.com" }),
+            SecuritySchemeKind::OpenIdConnect {
+                url: "https://example.com".to_owned(),
+            },
+        ),
+    ];
+    for (input, expected) in tests {
+        let kind: SecuritySchemeKind = serde_json::from_value(input.clone()).unwrap();
+        assert_eq!(kind, expected);
+        assert_eq!(serde_json::to_value(&kind).unwrap(), input);
+        let scheme = SecurityScheme::from(kind.clone());
+        assert_eq!(serde_json::to_value(&scheme).unwrap(), input);
+        assert_eq!(SecuritySchemeKind::try_from(scheme).unwrap(), kind);
+    }
+
+    let input = json!({ "type": "apiKey", "name": "api_key" });
+    assert!(serde_json::from_value::<SecuritySchemeKind>(input).is_err());
+}
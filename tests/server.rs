@@ -0,0 +1,105 @@
+#![cfg(feature = "json")]
+
+use std::collections::HashMap;
+
+use openapi::{HttpMethod, Server, ServerError, Spec};
+use serde_json::json;
+
+fn server() -> Server {
+    serde_json::from_value(json!({
+        "url": "https://{username}.example.com:{port}/{basePath}",
+        "variables": {
+            "username": { "default": "demo" },
+            "port": { "enum": ["8443", "443"], "default": "8443" },
+            "basePath": { "default": "v2" },
+        },
+    }))
+    .unwrap()
+}
+
+#[test]
+fn resolve_url() {
+    let server = server();
+    let url = server.resolve_url(&HashMap::new()).unwrap();
+    assert_eq!(url, "https://demo.example.com:8443/v2");
+
+    let overrides = HashMap::from([
+        ("username".to_owned(), "thomas".to_owned()),
+        ("port".to_owned(), "443".to_owned()),
+    ]);
+    let url = server.resolve_url(&overrides).unwrap();
+    assert_eq!(url, "https://thomas.example.com:443/v2");
+}
+
+#[test]
+fn resolve_url_errors() {
+    let server = server();
+    let overrides = HashMap::from([("port".to_owned(), "80".to_owned())]);
+    assert!(matches!(
+        server.resolve_url(&overrides),
+        Err(ServerError::InvalidValue { .. })
+    ));
+    let overrides = HashMap::from([("unknown".to_owned(), "1".to_owned())]);
+    assert!(matches!(
+        server.resolve_url(&overrides),
+        Err(ServerError::UnknownVariable(name)) if name == "unknown"
+    ));
+}
+
+#[test]
+#[cfg(feature = "url")]
+fn parsed_url() {
+    let url = server().parsed_url().unwrap();
+    assert_eq!(url.host_str(), Some("demo.example.com"));
+    assert_eq!(url.port(), Some(8443));
+    assert_eq!(url.path(), "/v2");
+
+    let relative = Server {
+        url: "/v1".to_owned(),
+        description: None,
+        variables: HashMap::new(),
+        extensions: HashMap::new(),
+    };
+    assert!(matches!(
+        relative.parsed_url(),
+        Err(ServerError::InvalidUrl(
+            url::ParseError::RelativeUrlWithoutBase
+        ))
+    ));
+}
+
+#[test]
+fn servers_for() {
+    let spec: Spec = serde_json::from_value(json!({
+        "openapi": "3.1.0",
+        "info": { "title": "Test", "version": "1.0.0" },
+        "paths": {
+            "/pets": {
+                "get": {},
+                "post": { "servers": [{ "url": "https://upload.example.com" }] },
+                "servers": [{ "url": "https://pets.example.com/v2/" }],
+            },
+            "/users": { "get": {} },
+        },
+    }))
+    .unwrap();
+
+    assert_eq!(spec.default_server().url, "/");
+    assert_eq!(spec.base_path().unwrap(), "");
+    let servers = spec.servers_for("/users", HttpMethod::Get).unwrap();
+    assert_eq!(servers.len(), 1);
+    assert_eq!(servers[0].url, "/");
+    let servers = spec.servers_for("/pets", HttpMethod::Get).unwrap();
+    assert_eq!(servers[0].url, "https://pets.example.com/v2/");
+    let servers = spec.servers_for("/pets", HttpMethod::Post).unwrap();
+    assert_eq!(servers[0].url, "https://upload.example.com");
+    assert!(spec.servers_for("/pets", HttpMethod::Delete).is_none());
+    assert!(spec.servers_for("/unknown", HttpMethod::Get).is_none());
+
+    let mut spec = spec;
+    spec.servers = vec![server()];
+    assert_eq!(spec.default_server().url, server().url);
+    assert_eq!(spec.base_path().unwrap(), "/v2");
+    let servers = spec.servers_for("/users", HttpMethod::Get).unwrap();
+    assert_eq!(*servers, spec.servers[..]);
+}
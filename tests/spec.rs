@@ -0,0 +1,1163 @@
+#![cfg(feature = "yaml")]
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use openapi::{
+    read_from_file, BundleError, HttpMethod, MergePolicy, ParseError, ParseOptions, Schema, Spec,
+    Tag, Value, Version,
+};
+
+const PETSTORE: &str = "tests/data/petstore.yaml";
+
+#[test]
+fn parse_equal() {
+    let spec1 = read_from_file(PETSTORE).unwrap();
+    let spec2 = read_from_file(PETSTORE).unwrap();
+    assert_eq!(spec1, spec2);
+
+    let mut spec3 = spec1.clone();
+    spec3.info.version = "2.0.0".to_owned();
+    assert_ne!(spec1, spec3);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn read_from_reader() {
+    let expected = read_from_file(PETSTORE).unwrap();
+    let yaml = fs::read_to_string(PETSTORE).unwrap();
+    let json = serde_json::to_string(&expected).unwrap();
+
+    // First non-whitespace byte is `{`, parsed as JSON.
+    let spec = openapi::read_from_reader(json.as_bytes()).unwrap();
+    assert_eq!(spec, expected);
+    let input = format!(" \n\t\r\n{}", json);
+    let spec = openapi::read_from_reader(input.as_bytes()).unwrap();
+    assert_eq!(spec, expected);
+
+    // Anything else is parsed as YAML.
+    let spec = openapi::read_from_reader(yaml.as_bytes()).unwrap();
+    assert_eq!(spec, expected);
+    let input = format!("\n\n{}", yaml);
+    let spec = openapi::read_from_reader(input.as_bytes()).unwrap();
+    assert_eq!(spec, expected);
+
+    // Detection errors come from the detected parser.
+    for input in ["  { invalid", "\n[]"] {
+        let err = openapi::read_from_reader(input.as_bytes()).unwrap_err();
+        let err = err.into_inner().unwrap();
+        assert!(err.is::<serde_json::Error>(), "{:?}", err);
+    }
+    let err = openapi::read_from_reader(&b"\n  - invalid"[..]).unwrap_err();
+    let err = err.into_inner().unwrap();
+    assert!(err.is::<serde_yaml::Error>(), "{:?}", err);
+}
+
+#[test]
+fn merge() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    let mut other = spec.clone();
+    // Identical definitions are allowed.
+    spec.merge(other.clone()).unwrap();
+    assert_eq!(spec, read_from_file(PETSTORE).unwrap());
+
+    other
+        .components
+        .schemas
+        .insert("Pet".to_owned(), Schema::default());
+    other
+        .components
+        .schemas
+        .insert("Owner".to_owned(), Schema::default());
+    let err = spec.merge(other.clone()).unwrap_err();
+    assert_eq!(err.location, "/components/schemas/Pet");
+    assert!(!spec.components.schemas.contains_key("Owner"));
+
+    spec.merge_with(other, MergePolicy::LastWins).unwrap();
+    assert_eq!(spec.components.schemas["Pet"], Schema::default());
+    assert!(spec.components.schemas.contains_key("Owner"));
+}
+
+#[test]
+fn bundle() {
+    let spec = read_from_file("tests/data/bundle/openapi.yaml").unwrap();
+    let spec = spec.bundle(Path::new("tests/data/bundle")).unwrap();
+    assert!(spec.check_references().is_empty());
+
+    let components = &spec.components;
+    let mut schemas: Vec<&str> = components.schemas.keys().map(|s| &**s).collect();
+    schemas.sort_unstable();
+    assert_eq!(schemas, ["Error", "owner", "pet"]);
+    let pet = &components.schemas["pet"];
+    let properties = pet.properties.as_ref().unwrap();
+    let owner = properties["owner"].r#ref.as_deref();
+    assert_eq!(owner, Some("#/components/schemas/owner"));
+    let parent = properties["parent"].r#ref.as_deref();
+    assert_eq!(parent, Some("#/components/schemas/pet"));
+    assert!(components.responses.contains_key("Error"));
+    assert!(components.path_items.contains_key("owners"));
+    assert_eq!(
+        spec.paths["/owners"].r#ref.as_deref(),
+        Some("#/components/pathItems/owners")
+    );
+}
+
+#[test]
+fn deny_unknown_fields() {
+    let options = ParseOptions::new().deny_unknown_fields(true);
+    options.read_from_file(PETSTORE).unwrap();
+
+    let input = r#"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+  x-internal: true
+paths:
+  /pets:
+    get:
+      descriptions: Typo
+      responses:
+        '200':
+          description: OK
+"#;
+    Spec::from_yaml_str(input).unwrap();
+    match options.from_yaml_str(input) {
+        Err(ParseError::UnknownFields(fields)) => {
+            assert_eq!(fields, ["/paths/~1pets/get/descriptions"]);
+        }
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn deny_unknown_fields_definitions() {
+    let options = ParseOptions::new().deny_unknown_fields(true);
+    let input = r#"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+components:
+  schemas:
+    A:
+      definitions:
+        B:
+          type: string
+"#;
+    let spec = options.from_yaml_str(input).unwrap();
+    assert!(spec.components.schemas["A"].defs.contains_key("B"));
+}
+
+#[test]
+fn deny_unknown_fields_callbacks() {
+    let options = ParseOptions::new().deny_unknown_fields(true);
+    let input = r#"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+paths:
+  /a:
+    post:
+      callbacks:
+        cb:
+          '{$request.body#/url}':
+            post:
+              responses:
+                '200':
+                  description: OK
+      responses:
+        '200':
+          description: OK
+"#;
+    let spec = options.from_yaml_str(input).unwrap();
+    let operation = spec.paths["/a"].post.as_ref().unwrap();
+    let callback = operation.callbacks["cb"].object.as_ref().unwrap();
+    let expressions: Vec<&str> = callback.expressions.keys().map(String::as_str).collect();
+    assert_eq!(expressions, ["{$request.body#/url}"]);
+}
+
+#[test]
+fn schemas() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    let alias = Schema {
+        r#ref: Some("#/components/schemas/Pet".to_owned()),
+        ..Schema::default()
+    };
+    spec.components.schemas.insert("Alias".to_owned(), alias);
+
+    assert_eq!(spec.schemas().count(), spec.components.schemas.len());
+    assert!(spec.schema("Alias").unwrap().r#ref.is_some());
+    assert_eq!(spec.resolved_schema("Alias"), spec.schema("Pet"));
+    assert_eq!(spec.resolved_schema("Pet"), spec.schema("Pet"));
+    assert!(spec.resolved_schema("Missing").is_none());
+}
+
+#[test]
+fn undeclared_security_scheme() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    assert!(spec.validate().is_ok());
+    let requirement = [("missing".to_owned(), Vec::new())].into_iter().collect();
+    spec.security.push(requirement);
+    let errors = spec.validate().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].location, "/security/0/missing");
+}
+
+#[test]
+fn retain_operations() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    let internal = [
+        ("/pets", HttpMethod::Post, true),
+        ("/pets", HttpMethod::Get, false),
+        ("/pets/{petId}", HttpMethod::Get, true),
+    ];
+    for (path, method, value) in internal {
+        let operation = spec.paths[path].operation_mut(method).unwrap();
+        operation
+            .extensions
+            .insert("x-internal".to_owned(), Value::Bool(value));
+    }
+
+    spec.remove_operations_with_extension("x-internal");
+    let operations: Vec<_> = spec.operations().map(|(p, m, _)| (p, m)).collect();
+    assert_eq!(operations, [("/pets", HttpMethod::Get)]);
+
+    spec.retain_operations(|_, method, _| method != HttpMethod::Get);
+    assert!(spec.paths.is_empty());
+}
+
+#[test]
+fn prune_unused_components() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    assert_eq!(spec.prune_unused_components(), 0);
+
+    // Unused, but referencing each other.
+    let reference = |name: &str| Schema {
+        r#ref: Some(format!("#/components/schemas/{}", name)),
+        ..Schema::default()
+    };
+    let schemas = &mut spec.components.schemas;
+    schemas.insert("A".to_owned(), reference("B"));
+    schemas.insert("B".to_owned(), reference("A"));
+    assert_eq!(spec.prune_unused_components(), 2);
+
+    spec.retain_operations(|path, _, _| path == "/pets/{petId}");
+    assert_eq!(spec.prune_unused_components(), 1);
+    assert!(!spec.components.schemas.contains_key("Pets"));
+    assert!(spec.components.schemas.contains_key("Pet"));
+    assert!(spec.components.schemas.contains_key("Error"));
+}
+
+#[test]
+fn content_type_header() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    let operation = spec.paths["/pets"].operation_mut(HttpMethod::Get).unwrap();
+    let responses = operation.responses.as_mut().unwrap();
+    let response = responses.response["200"].object.as_mut().unwrap();
+    let header = response.headers["x-next"].clone();
+    response.headers.insert("content-type".to_owned(), header);
+
+    let errors = spec.validate().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].location,
+        "/paths/~1pets/get/responses/200/headers/content-type"
+    );
+}
+
+#[test]
+fn max_nodes() {
+    // Expands into 10^9 strings.
+    let input = r#"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+x-a: &a ["lol", "lol", "lol", "lol", "lol", "lol", "lol", "lol", "lol", "lol"]
+x-b: &b [*a, *a, *a, *a, *a, *a, *a, *a, *a, *a]
+x-c: &c [*b, *b, *b, *b, *b, *b, *b, *b, *b, *b]
+x-d: &d [*c, *c, *c, *c, *c, *c, *c, *c, *c, *c]
+x-e: &e [*d, *d, *d, *d, *d, *d, *d, *d, *d, *d]
+x-f: &f [*e, *e, *e, *e, *e, *e, *e, *e, *e, *e]
+x-g: &g [*f, *f, *f, *f, *f, *f, *f, *f, *f, *f]
+x-h: &h [*g, *g, *g, *g, *g, *g, *g, *g, *g, *g]
+x-i: &i [*h, *h, *h, *h, *h, *h, *h, *h, *h, *h]
+"#;
+    match Spec::from_yaml_str(input) {
+        Err(ParseError::TooManyNodes) => {}
+        res => panic!("unexpected result: {:?}", res.map(|_| ())),
+    }
+
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    let schema = Schema {
+        r#ref: Some("laughs.yaml#/i".to_owned()),
+        ..Schema::default()
+    };
+    spec.components.schemas.insert("Laughs".to_owned(), schema);
+    match spec.bundle(Path::new("tests/data/bundle")) {
+        Err(BundleError::Parse {
+            err: ParseError::TooManyNodes,
+            ..
+        }) => {}
+        res => panic!("unexpected result: {:?}", res.map(|_| ())),
+    }
+
+    let options = ParseOptions::new().max_nodes(10);
+    let err = options.read_from_file(PETSTORE).unwrap_err();
+    assert_eq!(err.to_string(), "document contains too many values");
+    let options = ParseOptions::new().max_nodes(10_000);
+    options.read_from_file(PETSTORE).unwrap();
+}
+
+#[test]
+fn file_extensions() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    let expected = read_from_file(PETSTORE).unwrap();
+    for name in ["petstore.yml", "petstore.YAML", "petstore.Yml"] {
+        let path = dir.join(name);
+        fs::copy(PETSTORE, &path).unwrap();
+        assert_eq!(read_from_file(&path).unwrap(), expected, "{}", name);
+    }
+    assert!(read_from_file(dir.join("petstore.txt")).is_err());
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn detect_format() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    let expected = read_from_file(PETSTORE).unwrap();
+    let path = dir.join("openapi");
+    fs::copy(PETSTORE, &path).unwrap();
+    assert_eq!(read_from_file(&path).unwrap(), expected);
+
+    let path = dir.join("openapi.txt");
+    fs::write(&path, serde_json::to_string(&expected).unwrap()).unwrap();
+    assert_eq!(read_from_file(&path).unwrap(), expected);
+
+    // Returns the error of the detected parser.
+    fs::write(&path, "{ invalid").unwrap();
+    let err = read_from_file(&path).unwrap_err();
+    let err = err.into_inner().unwrap();
+    assert!(err.is::<serde_json::Error>(), "{:?}", err);
+}
+
+#[test]
+#[cfg(feature = "url")]
+fn invalid_urls() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    assert!(spec.validate().is_ok());
+
+    let license = spec.info.license.as_mut().unwrap();
+    license.identifier = None;
+    license.url = Some("not a url".to_owned());
+    spec.servers[0].url = "http://[::1".to_owned();
+    let errors = spec.validate().unwrap_err();
+    let locations: Vec<&str> = errors.iter().map(|e| &*e.location).collect();
+    assert_eq!(locations, ["/info/license/url", "/servers/0/url"]);
+}
+
+#[test]
+fn contact_email() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    let contact = spec.info.contact.get_or_insert_with(Default::default);
+    contact.email = Some("info@example.com".to_owned());
+    assert!(spec.validate().is_ok());
+
+    for email in ["not-an-email", "@example.com", "info@"] {
+        spec.info.contact.as_mut().unwrap().email = Some(email.to_owned());
+        let errors = spec.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].location, "/info/contact/email");
+    }
+}
+
+#[test]
+fn mutually_exclusive_examples() {
+    let input = r##"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      parameters:
+        - name: limit
+          in: query
+          schema:
+            type: integer
+          example: 10
+          examples:
+            ten:
+              value: 10
+      responses:
+        "200":
+          description: Pets.
+          content:
+            application/json:
+              example: []
+              examples:
+                empty:
+                  $ref: "#/components/examples/Empty"
+components:
+  examples:
+    Empty:
+      value: []
+      externalValue: https://example.com/empty.json
+"##;
+    let spec = Spec::from_yaml_str(input).unwrap();
+    let errors = spec.validate().unwrap_err();
+    let mut locations: Vec<&str> = errors.iter().map(|e| &*e.location).collect();
+    locations.sort_unstable();
+    assert_eq!(
+        locations,
+        [
+            "/components/examples/Empty",
+            "/paths/~1pets/get/parameters/0/example",
+            "/paths/~1pets/get/responses/200/content/application~1json/example",
+        ]
+    );
+}
+
+#[test]
+fn content_types() {
+    let spec = read_from_file(PETSTORE).unwrap();
+    let list = spec.paths["/pets"].operation(HttpMethod::Get).unwrap();
+    assert!(list.request_content_types(&spec).is_empty());
+    assert!(!list.body_required(&spec));
+    assert_eq!(
+        list.response_content_types(&spec, 200),
+        ["application/json"]
+    );
+
+    let create = spec.paths["/pets"].operation(HttpMethod::Post).unwrap();
+    assert_eq!(create.request_content_types(&spec), ["application/json"]);
+    assert!(create.body_required(&spec));
+    assert!(create.response_content_types(&spec, 201).is_empty());
+    // Falls back to the default response, which is a reference.
+    assert_eq!(
+        create.response_content_types(&spec, 500),
+        ["application/json"]
+    );
+}
+
+#[test]
+fn tags() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    assert_eq!(spec.used_tags(), HashSet::from(["pets"]));
+    assert_eq!(spec.undeclared_tags(), ["pets"]);
+    assert!(spec.unused_declared_tags().is_empty());
+
+    spec.tags.push(Tag {
+        name: "pets".to_owned(),
+        description: None,
+        external_docs: None,
+        extensions: HashMap::new(),
+    });
+    spec.tags.push(Tag {
+        name: "stores".to_owned(),
+        description: None,
+        external_docs: None,
+        extensions: HashMap::new(),
+    });
+    assert!(spec.undeclared_tags().is_empty());
+    assert_eq!(spec.unused_declared_tags(), ["stores"]);
+}
+
+#[test]
+fn duplicate_tags_and_server_variables() {
+    let input = r#"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+servers:
+  - url: https://{region}.example.com
+    variables:
+      region:
+        enum: [eu, us]
+        default: asia
+      version:
+        enum: []
+        default: v1
+tags:
+  - name: pets
+  - name: stores
+  - name: pets
+"#;
+    let spec = Spec::from_yaml_str(input).unwrap();
+    let errors = spec.validate().unwrap_err();
+    let mut errors: Vec<(&str, &str)> =
+        errors.iter().map(|e| (&*e.location, &*e.message)).collect();
+    errors[1..].sort_unstable();
+    assert_eq!(
+        errors,
+        [
+            (
+                "/tags/2/name",
+                "duplicate tag name `pets`, also used in /tags/0"
+            ),
+            (
+                "/servers/0/variables/region/default",
+                "default `asia` is not one of the `enum` values"
+            ),
+            (
+                "/servers/0/variables/version/enum",
+                "must contain at least one value"
+            ),
+        ]
+    );
+    let variable = &spec.servers[0].variables["version"];
+    assert_eq!(variable.r#enum.as_deref(), Some(&[][..]));
+}
+
+#[test]
+fn ensure_operation_ids() {
+    let input = r#"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+paths:
+  /pets/{id}:
+    get:
+      responses:
+        "200":
+          description: Pet.
+    delete:
+      operationId: deletePet
+      responses:
+        "204":
+          description: Deleted.
+  /pets/{id}/:
+    get:
+      responses:
+        "200":
+          description: Pet.
+"#;
+    let mut spec = Spec::from_yaml_str(input).unwrap();
+    let assigned = spec.ensure_operation_ids();
+    assert_eq!(
+        assigned,
+        HashMap::from([
+            (
+                "getPetsId".to_owned(),
+                ("/pets/{id}".to_owned(), HttpMethod::Get)
+            ),
+            (
+                "getPetsId2".to_owned(),
+                ("/pets/{id}/".to_owned(), HttpMethod::Get)
+            ),
+        ])
+    );
+    assert_eq!(
+        spec.operation_by_id("deletePet").unwrap().1,
+        HttpMethod::Delete
+    );
+    assert!(spec.ensure_operation_ids().is_empty());
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn stream_json_paths() {
+    let spec = read_from_file(PETSTORE).unwrap();
+    let input = serde_json::to_vec(&spec).unwrap();
+    let mut paths = Vec::new();
+    openapi::stream_json_paths(&*input, |path, path_item| paths.push((path, path_item))).unwrap();
+    let expected: Vec<_> = spec.paths.into_iter().collect();
+    assert_eq!(paths, expected);
+
+    let err = openapi::stream_json_paths(&b"{\"paths\": []}"[..], |_, _| {}).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn resolve_escaped_reference() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    let pet = spec.components.schemas["Pet"].clone();
+    spec.components
+        .schemas
+        .insert("weird/name~".to_owned(), pet.clone());
+    let schema = spec.resolve_ref::<Schema>("#/components/schemas/weird~1name~0");
+    assert_eq!(schema, Some(&pet));
+    assert!(spec
+        .resolve_ref::<Schema>("#/components/schemas/weird/name~")
+        .is_none());
+    assert!(spec.resolve_ref::<Schema>("#").is_none());
+
+    let reference = Schema {
+        r#ref: Some("#/components/schemas/weird~1name~0".to_owned()),
+        ..Schema::default()
+    };
+    spec.components.schemas.insert("Ref".to_owned(), reference);
+    assert!(spec.check_references().is_empty());
+}
+
+#[test]
+fn invalid_component_names() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    let pet = spec.components.schemas["Pet"].clone();
+    spec.components.schemas.insert("My Schema".to_owned(), pet);
+    let errors = spec.validate().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].location, "/components/schemas/My Schema");
+}
+
+#[test]
+#[cfg(feature = "regex")]
+fn invalid_patterns() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    let pet = spec.components.schemas.get_mut("Pet").unwrap();
+    pet.pattern = Some("^[a-z]+$".to_owned());
+    assert!(pet.compiled_pattern().unwrap().unwrap().is_match("doggie"));
+    assert!(spec.validate().is_ok());
+
+    let pet = spec.components.schemas.get_mut("Pet").unwrap();
+    pet.pattern = Some("^(?!admin)".to_owned());
+    assert!(pet.compiled_pattern().unwrap().is_err());
+    let pattern = pet.pattern.clone().unwrap();
+    pet.pattern_properties
+        .insert("a/[".to_owned(), Schema::default());
+    let errors = spec.validate().unwrap_err();
+    let locations: Vec<&str> = errors.iter().map(|e| &*e.location).collect();
+    assert_eq!(
+        locations,
+        [
+            "/components/schemas/Pet/pattern",
+            "/components/schemas/Pet/patternProperties/a~1[",
+        ]
+    );
+    assert!(errors[0].message.contains(&pattern));
+}
+
+#[test]
+fn info() {
+    let spec = read_from_file(PETSTORE).unwrap();
+    assert_eq!(spec.title(), "Swagger Petstore");
+    assert_eq!(spec.api_version(), "1.0.0");
+    assert_eq!(spec.openapi_version(), Version::OpenApi3_1(0));
+    assert_eq!(spec.openapi_version().to_string(), "3.1.0");
+}
+
+#[test]
+fn patch_version() {
+    let input = r#"
+openapi: 3.1.1
+info:
+  title: Test
+  version: 1.0.0
+"#;
+    let spec = Spec::from_yaml_str(input).unwrap();
+    assert_eq!(spec.openapi, Version::OpenApi3_1(1));
+    let output = serde_yaml::to_string(&spec).unwrap();
+    assert!(output.contains("openapi: 3.1.1"), "{}", output);
+    assert_eq!(Spec::from_yaml_str(&output).unwrap(), spec);
+
+    let input = input.replace("3.1.1", "2.0");
+    let err = Spec::from_yaml_str(&input).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("unsupported OpenAPI version `2.0`"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn operation_map_json() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    let (_, _, operation) = spec.operations_mut().next().unwrap();
+    operation.operation_id = None;
+    let map = spec.operation_map_json();
+    let operations = map.as_array().unwrap();
+    assert_eq!(operations.len(), spec.operations().count());
+    assert_eq!(
+        operations[0],
+        serde_json::json!({
+            "operationId": "getPets",
+            "method": "get",
+            "path": "/pets",
+            "tags": ["pets"],
+            "deprecated": false,
+        })
+    );
+    assert_eq!(operations[1]["operationId"], "createPets");
+}
+
+#[test]
+fn referenced_path_items() {
+    let input = r##"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+paths:
+  /pets:
+    $ref: '#/components/pathItems/Pets'
+  /dogs:
+    $ref: '#/components/pathItems/Dogs'
+    post:
+      operationId: createDog
+      responses:
+        '201':
+          description: Created
+  /cats:
+    $ref: '#/components/pathItems/Missing'
+components:
+  pathItems:
+    Pets:
+      $ref: '#/components/pathItems/Animals'
+    Animals:
+      get:
+        operationId: listPets
+        responses:
+          '200':
+            description: OK
+    Dogs:
+      get:
+        operationId: listDogs
+        responses:
+          '200':
+            description: OK
+"##;
+    let spec = Spec::from_yaml_str(input).unwrap();
+    let pets = spec.paths["/pets"].resolve(&spec);
+    assert_eq!(pets, &spec.components.path_items["Animals"]);
+    let dogs = &spec.paths["/dogs"];
+    assert_eq!(dogs.resolve(&spec), dogs);
+    let cats = &spec.paths["/cats"];
+    assert_eq!(cats.resolve(&spec), cats);
+
+    let operations: Vec<_> = spec
+        .operations()
+        .map(|(path, method, operation)| (path, method, operation.operation_id.as_deref()))
+        .collect();
+    assert_eq!(
+        operations,
+        [
+            ("/pets", HttpMethod::Get, Some("listPets")),
+            ("/dogs", HttpMethod::Post, Some("createDog")),
+        ]
+    );
+    assert!(spec.servers_for("/pets", HttpMethod::Get).is_some());
+}
+
+#[test]
+fn schema_dialect() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    assert_eq!(
+        spec.schema_dialect(),
+        "https://spec.openapis.org/oas/3.1/dialect/base"
+    );
+    let dialect = "https://json-schema.org/draft/2020-12/schema";
+    spec.json_schema_dialect = Some(dialect.to_owned());
+    assert_eq!(spec.schema_dialect(), dialect);
+    spec.components.schemas.get_mut("Pet").unwrap().schema = Some(format!("{}#", dialect));
+    assert!(spec.validate().is_ok());
+
+    spec.json_schema_dialect = Some("http://json-schema.org/draft-07/schema#".to_owned());
+    spec.components.schemas.get_mut("Pet").unwrap().schema =
+        Some("https://json-schema.org/draft/2019-09/schema".to_owned());
+    let errors = spec.validate().unwrap_err();
+    let locations: Vec<&str> = errors.iter().map(|e| &*e.location).collect();
+    assert_eq!(
+        locations,
+        ["/jsonSchemaDialect", "/components/schemas/Pet/$schema"]
+    );
+}
+
+#[test]
+fn referenced_path_items_mut() {
+    let input = r##"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          description: OK
+  /internal:
+    $ref: '#/components/pathItems/Internal'
+  /internal2:
+    $ref: '#/components/pathItems/Internal'
+  /users:
+    $ref: '#/components/pathItems/Users'
+components:
+  pathItems:
+    Internal:
+      get:
+        x-internal: true
+        responses:
+          '200':
+            description: OK
+    Users:
+      get:
+        responses:
+          '200':
+            description: OK
+"##;
+    let mut spec = Spec::from_yaml_str(input).unwrap();
+    assert_eq!(spec.operations_mut().count(), 3);
+
+    let mut ids = spec.clone();
+    let assigned = ids.ensure_operation_ids();
+    assert_eq!(assigned.len(), 3);
+    assert_eq!(
+        assigned["getInternal"],
+        ("/internal".to_owned(), HttpMethod::Get)
+    );
+    let operation = ids.components.path_items["Internal"].get.as_ref().unwrap();
+    assert_eq!(operation.operation_id.as_deref(), Some("getInternal"));
+
+    spec.remove_operations_with_extension("x-internal");
+    let paths: Vec<&str> = spec.paths.keys().map(String::as_str).collect();
+    assert_eq!(paths, ["/pets", "/users"]);
+    assert!(spec.operations().all(|(_, _, op)| op.extensions.is_empty()));
+    assert!(spec.components.path_items["Internal"].get.is_none());
+}
+
+#[test]
+fn prune_discriminator_mapping() {
+    let input = r##"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [petType]
+      properties:
+        petType:
+          type: string
+      discriminator:
+        propertyName: petType
+        mapping:
+          cat: '#/components/schemas/Cat'
+          dog: Dog
+    Cat:
+      allOf:
+        - $ref: '#/components/schemas/Pet'
+        - type: object
+          properties:
+            lives:
+              type: integer
+    Dog:
+      allOf:
+        - $ref: '#/components/schemas/Pet'
+    Unused:
+      type: string
+"##;
+    let mut spec = Spec::from_yaml_str(input).unwrap();
+    assert!(spec.check_references().is_empty());
+    assert_eq!(spec.prune_unused_components(), 1);
+    let mut names: Vec<&str> = spec.components.schemas.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["Cat", "Dog", "Pet"]);
+    assert!(spec.check_references().is_empty());
+
+    spec.components.schemas.remove("Cat");
+    spec.components.schemas.remove("Dog");
+    let mut broken: Vec<_> = spec
+        .check_references()
+        .into_iter()
+        .map(|broken| (broken.location, broken.reference))
+        .collect();
+    broken.sort_unstable();
+    let location = "/components/schemas/Pet/discriminator/mapping";
+    assert_eq!(
+        broken,
+        [
+            (location.to_owned(), "#/components/schemas/Cat".to_owned()),
+            (location.to_owned(), "Dog".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn inline_object_errors() {
+    let input = r#"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          content: {}
+"#;
+    let err = Spec::from_yaml_str(input).unwrap_err();
+    assert!(
+        err.to_string().contains("missing field `description`"),
+        "{}",
+        err
+    );
+
+    let input = r#"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      parameters:
+        - name: id
+          in: body
+      responses:
+        '200':
+          description: OK
+"#;
+    let err = Spec::from_yaml_str(input).unwrap_err();
+    assert!(
+        err.to_string().contains("unknown variant `body`"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn responses_and_callback_extensions() {
+    let input = r#"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+paths:
+  /pets:
+    post:
+      callbacks:
+        created:
+          x-callback: true
+      responses:
+        '200':
+          description: OK
+        x-responses: 1
+"#;
+    let spec = Spec::from_yaml_str(input).unwrap();
+    let operation = spec.paths["/pets"].post.as_ref().unwrap();
+    let responses = operation.responses.as_ref().unwrap();
+    let codes: Vec<&str> = responses.response.keys().map(String::as_str).collect();
+    assert_eq!(codes, ["200"]);
+    assert_eq!(
+        responses.extensions["x-responses"],
+        Value::Number(1u64.into())
+    );
+    let callback = operation.callbacks["created"].object.as_ref().unwrap();
+    assert_eq!(callback.extensions["x-callback"], Value::Bool(true));
+
+    let output = serde_yaml::to_value(&spec).unwrap();
+    let operation = &output["paths"]["/pets"]["post"];
+    assert_eq!(
+        operation["responses"]["x-responses"],
+        serde_yaml::Value::from(1)
+    );
+    assert_eq!(
+        operation["callbacks"]["created"]["x-callback"],
+        serde_yaml::Value::from(true)
+    );
+}
+
+#[test]
+fn check_deep_references() {
+    let input = r##"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+components:
+  schemas:
+    Pet:
+      properties:
+        id:
+          type: integer
+    Owner:
+      properties:
+        petId:
+          $ref: '#/components/schemas/Pet/properties/id'
+        missing:
+          $ref: '#/components/schemas/Missing/properties/id'
+"##;
+    let spec = Spec::from_yaml_str(input).unwrap();
+    let broken: Vec<_> = spec
+        .check_references()
+        .into_iter()
+        .map(|broken| (broken.location, broken.reference))
+        .collect();
+    assert_eq!(
+        broken,
+        [(
+            "/components/schemas/Owner/properties/missing".to_owned(),
+            "#/components/schemas/Missing/properties/id".to_owned()
+        )]
+    );
+}
+
+#[test]
+fn operations() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    let operations: Vec<_> = spec
+        .operations()
+        .map(|(path, method, op)| (path, method, op.operation_id.as_deref().unwrap()))
+        .collect();
+    assert_eq!(
+        operations,
+        [
+            ("/pets", HttpMethod::Get, "listPets"),
+            ("/pets", HttpMethod::Post, "createPets"),
+            ("/pets/{petId}", HttpMethod::Get, "showPetById"),
+        ]
+    );
+
+    let methods: Vec<_> = spec.paths["/pets"]
+        .operations()
+        .map(|(method, _)| method)
+        .collect();
+    assert_eq!(methods, [HttpMethod::Get, HttpMethod::Post]);
+
+    for (path, method, operation) in spec.operations_mut() {
+        operation.summary = Some(format!("{method} {path}"));
+    }
+    for (_, operation) in spec.paths["/pets"].operations_mut() {
+        operation.deprecated = true;
+    }
+    let operations: Vec<_> = spec
+        .operations()
+        .map(|(_, _, op)| (op.summary.as_deref().unwrap(), op.deprecated))
+        .collect();
+    assert_eq!(
+        operations,
+        [
+            ("get /pets", true),
+            ("post /pets", true),
+            ("get /pets/{petId}", false),
+        ]
+    );
+}
+
+#[test]
+fn operations_skip_webhooks() {
+    let input = r##"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+webhooks:
+  newPet:
+    post:
+      operationId: newPet
+      responses:
+        '200':
+          description: OK
+"##;
+    let mut spec = Spec::from_yaml_str(input).unwrap();
+    assert_eq!(spec.operations().count(), 0);
+    assert_eq!(spec.operations_mut().count(), 0);
+    assert_eq!(spec.webhooks["newPet"].operations().count(), 1);
+}
+
+#[test]
+fn path_item_operation() {
+    let mut spec = read_from_file(PETSTORE).unwrap();
+    let path_item = spec.paths.get_mut("/pets").unwrap();
+    for method in HttpMethod::ALL {
+        let expected = match method {
+            HttpMethod::Get => Some("listPets"),
+            HttpMethod::Post => Some("createPets"),
+            _ => None,
+        };
+        let operation = path_item.operation(method);
+        assert_eq!(
+            operation.and_then(|op| op.operation_id.as_deref()),
+            expected,
+            "{method}"
+        );
+        assert_eq!(
+            path_item.operation_mut(method).is_some(),
+            expected.is_some()
+        );
+    }
+
+    path_item
+        .operation_mut(HttpMethod::Post)
+        .unwrap()
+        .deprecated = true;
+    assert!(path_item.post.as_ref().unwrap().deprecated);
+    assert!(!path_item.get.as_ref().unwrap().deprecated);
+    assert!(path_item.operation_mut(HttpMethod::Delete).is_none());
+    assert!(path_item.delete.is_none());
+}
+
+#[test]
+fn operation_by_id() {
+    let spec = read_from_file(PETSTORE).unwrap();
+    let (path, method, operation) = spec.operation_by_id("createPets").unwrap();
+    assert_eq!((path, method), ("/pets", HttpMethod::Post));
+    assert_eq!(operation.summary.as_deref(), Some("Create a pet"));
+    assert!(spec.operation_by_id("deletePet").is_none());
+
+    let index = spec.build_operation_index();
+    let expected = HashMap::from([
+        ("listPets", ("/pets", HttpMethod::Get)),
+        ("createPets", ("/pets", HttpMethod::Post)),
+        ("showPetById", ("/pets/{petId}", HttpMethod::Get)),
+    ]);
+    assert_eq!(index, expected);
+}
+
+#[test]
+fn operation_by_id_duplicates() {
+    let input = r##"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      operationId: getPets
+    post: {}
+  /animals:
+    $ref: '#/components/pathItems/Animals'
+components:
+  pathItems:
+    Animals:
+      get:
+        operationId: getPets
+      put:
+        operationId: putAnimals
+"##;
+    let spec = Spec::from_yaml_str(input).unwrap();
+    let (path, method, _) = spec.operation_by_id("getPets").unwrap();
+    assert_eq!((path, method), ("/pets", HttpMethod::Get));
+    // Operations in referenced path items.
+    let (path, method, _) = spec.operation_by_id("putAnimals").unwrap();
+    assert_eq!((path, method), ("/animals", HttpMethod::Put));
+
+    let index = spec.build_operation_index();
+    let expected = HashMap::from([
+        ("getPets", ("/pets", HttpMethod::Get)),
+        ("putAnimals", ("/animals", HttpMethod::Put)),
+    ]);
+    assert_eq!(index, expected);
+}
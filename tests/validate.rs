@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use openapi::{
+    Parameter, ParameterLocation, ParameterStyle, Schema, SecurityScheme, SecuritySchemeIn,
+    SecuritySchemeType,
+};
+
+#[test]
+fn parameter() {
+    let parameter = Parameter {
+        name: "petId".to_owned(),
+        r#in: ParameterLocation::Path,
+        required: true,
+        schema: Some(Schema::default()),
+        ..Parameter::default()
+    };
+    assert!(parameter.validate().is_ok());
+
+    let invalid = Parameter {
+        required: false,
+        allow_reserved: true,
+        style: Some(ParameterStyle::Form),
+        ..parameter.clone()
+    };
+    let errors = invalid.validate().unwrap_err();
+    let locations: Vec<&str> = errors.iter().map(|err| &*err.location).collect();
+    assert_eq!(locations, ["/required", "/allowReserved", "/style"]);
+
+    let query = Parameter {
+        r#in: ParameterLocation::Query,
+        required: false,
+        allow_empty_value: true,
+        schema: None,
+        ..parameter
+    };
+    let errors = query.validate().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].location, "/schema");
+}
+
+#[test]
+fn security_scheme() {
+    let mut security_scheme = SecurityScheme {
+        r#type: SecuritySchemeType::ApiKey,
+        description: None,
+        name: None,
+        r#in: Some(SecuritySchemeIn::Header),
+        scheme: None,
+        bearer_format: None,
+        flows: None,
+        open_id_connect_url: None,
+        extensions: HashMap::new(),
+    };
+    let errors = security_scheme.validate().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].location, "/name");
+
+    security_scheme.name = Some("api_key".to_owned());
+    assert!(security_scheme.validate().is_ok());
+
+    security_scheme.r#type = SecuritySchemeType::Http;
+    let errors = security_scheme.validate().unwrap_err();
+    assert_eq!(errors[0].location, "/scheme");
+}
+
+#[test]
+#[cfg(feature = "yaml")]
+fn spec() {
+    let input = r#"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+  license:
+    name: MIT
+    identifier: MIT
+    url: https://opensource.org/licenses/MIT
+paths:
+  /pets/{petId}:
+    get:
+      operationId: getPet
+      responses: {}
+  /owners/{ownerId}:
+    parameters:
+      - name: ownerId
+        in: path
+        required: true
+        schema:
+          type: string
+    get:
+      operationId: getPet
+      responses:
+        '200':
+          description: OK
+"#;
+    let spec = openapi::Spec::from_yaml_str(input).unwrap();
+    let errors = spec.validate().unwrap_err();
+    let locations: Vec<&str> = errors.iter().map(|e| &*e.location).collect();
+    assert_eq!(
+        locations,
+        [
+            "/info/license",
+            "/paths/~1pets~1{petId}/get/parameters",
+            "/paths/~1pets~1{petId}/get/responses",
+            "/paths/~1owners~1{ownerId}/get/operationId",
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "yaml")]
+fn referenced_webhook() {
+    let input = r##"
+openapi: 3.1.0
+info:
+  title: Test
+  version: 1.0.0
+webhooks:
+  newPet:
+    $ref: '#/components/pathItems/NewPet'
+components:
+  pathItems:
+    NewPet:
+      post:
+        responses: {}
+"##;
+    let spec = openapi::Spec::from_yaml_str(input).unwrap();
+    let errors = spec.validate().unwrap_err();
+    let locations: Vec<&str> = errors.iter().map(|e| &*e.location).collect();
+    assert_eq!(locations, ["/webhooks/newPet/post/responses"]);
+}
@@ -0,0 +1,17 @@
+#![cfg(feature = "json")]
+
+use openapi::Value;
+use serde_json::json;
+
+#[test]
+fn round_trip() {
+    let input = json!({ "a": {}, "b": [1, -2, 3.5, "four", true, null] });
+    let value: Value = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&value).unwrap(), input);
+    assert_eq!(
+        value.to_string(),
+        r#"{"a":{},"b":[1,-2,3.5,"four",true,null]}"#
+    );
+    assert_eq!(Value::from(input.clone()), value);
+    assert_eq!(serde_json::Value::from(value), input);
+}
@@ -0,0 +1,138 @@
+#![cfg(feature = "yaml")]
+
+use openapi::{HttpMethod, Operation, Schema, Spec, Visitor, VisitorMut};
+
+const SPEC: &str = r##"
+openapi: 3.1.0
+info:
+  title: Visit
+  version: 1.0.0
+paths:
+  /pets/{id}:
+    parameters:
+      - name: id
+        in: path
+        required: true
+        schema:
+          type: integer
+    get:
+      operationId: getPet
+      responses:
+        "200":
+          description: Pet.
+          content:
+            application/json:
+              schema:
+                $ref: "#/components/schemas/Pet"
+    delete:
+      operationId: deletePet
+      responses:
+        "204":
+          $ref: "#/components/responses/Deleted"
+components:
+  schemas:
+    Pet:
+      description: A pet.
+      type: object
+      properties:
+        id:
+          description: Identifier.
+          type: integer
+        tags:
+          type: array
+          items:
+            type: string
+  responses:
+    Deleted:
+      description: Deleted.
+"##;
+
+#[derive(Default)]
+struct Collect<'s> {
+    operations: Vec<(String, HttpMethod, Option<&'s str>)>,
+    schemas: Vec<String>,
+    references: Vec<(String, &'s str)>,
+}
+
+impl<'s> Visitor<'s> for Collect<'s> {
+    fn visit_operation(&mut self, location: &str, method: HttpMethod, operation: &'s Operation) {
+        let id = operation.operation_id.as_deref();
+        self.operations.push((location.to_owned(), method, id));
+    }
+
+    fn visit_schema(&mut self, location: &str, _: &'s Schema) {
+        self.schemas.push(location.to_owned());
+    }
+
+    fn visit_reference(&mut self, location: &str, reference: &'s str) {
+        self.references.push((location.to_owned(), reference));
+    }
+}
+
+#[test]
+fn walk() {
+    let spec = Spec::from_yaml_str(SPEC).unwrap();
+    let mut collect = Collect::default();
+    spec.walk(&mut collect);
+
+    assert_eq!(
+        collect.operations,
+        [
+            (
+                "/paths/~1pets~1{id}/get".to_owned(),
+                HttpMethod::Get,
+                Some("getPet")
+            ),
+            (
+                "/paths/~1pets~1{id}/delete".to_owned(),
+                HttpMethod::Delete,
+                Some("deletePet")
+            ),
+        ]
+    );
+    assert_eq!(
+        collect.schemas,
+        [
+            "/paths/~1pets~1{id}/parameters/0/schema",
+            "/paths/~1pets~1{id}/get/responses/200/content/application~1json/schema",
+            "/components/schemas/Pet",
+            "/components/schemas/Pet/properties/id",
+            "/components/schemas/Pet/properties/tags",
+            "/components/schemas/Pet/properties/tags/items",
+        ]
+    );
+    assert_eq!(
+        collect.references,
+        [
+            (
+                "/paths/~1pets~1{id}/get/responses/200/content/application~1json/schema".to_owned(),
+                "#/components/schemas/Pet"
+            ),
+            (
+                "/paths/~1pets~1{id}/delete/responses/204".to_owned(),
+                "#/components/responses/Deleted"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn walk_mut() {
+    struct StripDescriptions;
+
+    impl VisitorMut for StripDescriptions {
+        fn visit_schema(&mut self, _: &str, schema: &mut Schema) {
+            schema.description = None;
+        }
+    }
+
+    let mut spec = Spec::from_yaml_str(SPEC).unwrap();
+    spec.walk_mut(&mut StripDescriptions);
+
+    let pet = &spec.components.schemas["Pet"];
+    assert_eq!(pet.description, None);
+    assert_eq!(pet.properties.as_ref().unwrap()["id"].description, None);
+    // Only schemas are changed.
+    let deleted = spec.components.responses["Deleted"].object.as_ref();
+    assert_eq!(deleted.unwrap().description, "Deleted.");
+}